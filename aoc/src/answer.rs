@@ -0,0 +1,177 @@
+//! Answers to puzzle parts, independent of the types solvers compute them in.
+
+use std::fmt::{self, Display};
+use std::hash::{Hash, Hasher};
+
+/// Answer to a puzzle part. Solvers return whatever type fits, which is converted into one of the
+/// variants below, so answers are compared by value rather than by how they happen to be printed.
+#[derive(Clone, Debug)]
+pub enum Answer {
+  /// Numeric answer, regardless of the integer type it was computed in.
+  Integer(i128),
+  /// Single line of text, e.g. letters of crates on top of stacks.
+  Text(String),
+  /// Multi-line art, e.g. a CRT screen, as rows without line breaks.
+  Grid(Vec<String>),
+  /// Letters read off a screen, see [crate::ocr::read], along with the screen itself. Compares
+  /// equal to the letters as [Answer::Text], so the screen doesn't have to be recorded.
+  Screen { text: String, image: Vec<String> },
+}
+
+impl Answer {
+  /// Reads an answer back from text, e.g. from a manifest. Integers in their canonical form become
+  /// [Answer::Integer], and text spanning several lines becomes [Answer::Grid].
+  pub fn parse(s: &str) -> Self {
+    if let Some(value) = s
+      .parse::<i128>()
+      .ok()
+      .filter(|value| value.to_string() == s)
+    {
+      return Answer::Integer(value);
+    }
+
+    if s.contains('\n') {
+      Answer::Grid(s.lines().map(str::to_string).collect())
+    } else {
+      Answer::Text(s.to_string())
+    }
+  }
+
+  pub fn is_multiline(&self) -> bool {
+    matches!(self, Answer::Grid(..))
+  }
+
+  /// Rows of the screen the answer was drawn on, if it was.
+  pub fn image(&self) -> Option<&[String]> {
+    match self {
+      | Answer::Grid(rows) | Answer::Screen { image: rows, .. } => Some(rows),
+      | _ => None,
+    }
+  }
+
+  /// The answer as compared: screens are compared by their letters only.
+  fn key(&self) -> Key<'_> {
+    match self {
+      | Answer::Integer(value) => Key::Integer(*value),
+      | Answer::Text(text) | Answer::Screen { text, .. } => Key::Text(text),
+      | Answer::Grid(rows) => Key::Grid(rows),
+    }
+  }
+}
+
+#[derive(PartialEq, Eq, Hash)]
+enum Key<'a> {
+  Integer(i128),
+  Text(&'a str),
+  Grid(&'a [String]),
+}
+
+impl PartialEq for Answer {
+  fn eq(&self, other: &Self) -> bool {
+    self.key() == other.key()
+  }
+}
+
+impl Eq for Answer {}
+
+impl Hash for Answer {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.key().hash(state);
+  }
+}
+
+impl Display for Answer {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      | Answer::Integer(value) => write!(f, "{value}"),
+      | Answer::Text(text) | Answer::Screen { text, .. } => write!(f, "{text}"),
+      | Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+    }
+  }
+}
+
+macro_rules! from_integer {
+  ($($ty:ty),*) => {
+    $(
+      impl From<$ty> for Answer {
+        fn from(value: $ty) -> Self {
+          Answer::Integer(value as i128)
+        }
+      }
+    )*
+  };
+}
+
+from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<i128> for Answer {
+  fn from(value: i128) -> Self {
+    Answer::Integer(value)
+  }
+}
+
+impl From<String> for Answer {
+  fn from(s: String) -> Self {
+    Answer::parse(&s)
+  }
+}
+
+impl From<&str> for Answer {
+  fn from(s: &str) -> Self {
+    Answer::parse(s)
+  }
+}
+
+impl From<char> for Answer {
+  fn from(c: char) -> Self {
+    Answer::parse(c.encode_utf8(&mut [0; 4]))
+  }
+}
+
+impl From<Vec<String>> for Answer {
+  fn from(rows: Vec<String>) -> Self {
+    Answer::Grid(rows)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_from() {
+    assert_eq!(Answer::from(42u32), Answer::Integer(42));
+    assert_eq!(Answer::from(-7isize), Answer::Integer(-7));
+    assert_eq!(Answer::from(u64::MAX), Answer::Integer(u64::MAX as i128));
+    assert_eq!(Answer::from("42"), Answer::from(42usize));
+    assert_eq!(
+      Answer::from("ZRLJGSCTR"),
+      Answer::Text("ZRLJGSCTR".to_string())
+    );
+    assert_eq!(Answer::from("007"), Answer::Text("007".to_string()));
+    assert_eq!(
+      Answer::from("##..\n#..#".to_string()),
+      Answer::Grid(vec!["##..".to_string(), "#..#".to_string()])
+    );
+  }
+
+  #[test]
+  fn test_screen() {
+    let screen = Answer::Screen {
+      text: "HI".to_string(),
+      image: vec!["#..#.###".to_string(), "####..#.".to_string()],
+    };
+
+    assert_eq!(screen, Answer::from("HI"));
+    assert_eq!(screen.to_string(), "HI");
+    assert_eq!(screen.image().map(<[String]>::len), Some(2));
+    assert_eq!(Answer::from("HI").image(), None);
+  }
+
+  #[test]
+  fn test_display() {
+    for answer in ["-42", "ZRLJGSCTR", "##..\n#..#"] {
+      assert_eq!(Answer::parse(answer).to_string(), answer);
+    }
+  }
+}
@@ -0,0 +1,209 @@
+//! Manifests of known answers. Every year keeps the answers to its bundled inputs in
+//! `answers.toml`, with a `[day_N]` table per day holding `part_1` and `part_2` strings:
+//!
+//! ```toml
+//! [day_1]
+//! part_1 = "70374"
+//! part_2 = "204610"
+//! ```
+//!
+//! TOML integers are accepted as well. Answers are read back as [Answer] values, so `"42"` and `42`
+//! are the same answer. The runner checks answers against it, and so do the `test_input` tests of
+//! every day.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+use crate::{Answer, Mode, Part, Solver};
+
+/// Outcome of checking an answer against the manifest.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Verdict {
+  Correct,
+  Wrong {
+    expected: Answer,
+  },
+  #[default]
+  Unknown,
+}
+
+impl Display for Verdict {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      | Verdict::Correct => write!(f, "correct"),
+      | Verdict::Wrong { .. } => write!(f, "wrong"),
+      | Verdict::Unknown => write!(f, "unknown"),
+    }
+  }
+}
+
+/// Known answers of a single year, keyed by day and part.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<u8, BTreeMap<Part, Answer>>);
+
+impl Answers {
+  /// Parses a manifest, see the [module docs](self) for the layout.
+  pub fn parse(manifest: &str) -> Result<Self, String> {
+    let table = manifest
+      .parse::<toml::Table>()
+      .map_err(|err| err.message().to_string())?;
+
+    let mut answers = Self::default();
+
+    for (key, value) in table {
+      let day = key
+        .strip_prefix("day_")
+        .and_then(|day| day.parse::<u8>().ok())
+        .ok_or_else(|| format!("expected a `[day_N]` table, got `{key}`"))?;
+
+      let parts = value
+        .as_table()
+        .ok_or_else(|| format!("expected `{key}` to be a table"))?;
+
+      for (key, value) in parts {
+        let part = match key.as_str() {
+          | "part_1" => Part::One,
+          | "part_2" => Part::Two,
+          | _ => {
+            return Err(format!(
+              "expected `part_1` or `part_2` in `day_{day}`, got `{key}`"
+            ))
+          },
+        };
+
+        let answer = match value {
+          | toml::Value::String(answer) => Answer::parse(answer),
+          | toml::Value::Integer(answer) => Answer::from(*answer),
+          | _ => {
+            return Err(format!(
+              "expected `day_{day}.{key}` to be a string or an integer"
+            ))
+          },
+        };
+
+        answers.insert(day, part, answer);
+      }
+    }
+
+    Ok(answers)
+  }
+
+  pub fn get(&self, day: u8, part: Part) -> Option<&Answer> {
+    self.0.get(&day)?.get(&part)
+  }
+
+  pub fn insert(&mut self, day: u8, part: Part, answer: Answer) {
+    self.0.entry(day).or_default().insert(part, answer);
+  }
+
+  /// Checks the answer to the given day and part.
+  pub fn verdict(&self, day: u8, part: Part, answer: &Answer) -> Verdict {
+    match self.get(day, part) {
+      | Some(expected) if expected == answer => Verdict::Correct,
+      | Some(expected) => {
+        Verdict::Wrong {
+          expected: expected.clone(),
+        }
+      },
+      | None => Verdict::Unknown,
+    }
+  }
+}
+
+/// Renders the manifest back to TOML, ordered by day and part. Answers are written as strings.
+impl Display for Answers {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (index, (day, parts)) in self.0.iter().enumerate() {
+      if index > 0 {
+        writeln!(f)?;
+      }
+
+      writeln!(f, "[day_{day}]")?;
+
+      for (part, answer) in parts {
+        writeln!(f, "part_{part} = {}", toml::Value::from(answer.to_string()))?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// Solves both parts of the given input and asserts that the answers match the manifest. Meant to
+/// be used by `test_input` tests, e.g.
+/// `aoc::answers::assert::<Day>(include_str!("../../answers.toml"), 1, INPUT)`.
+#[track_caller]
+pub fn assert<S: Solver>(manifest: &str, day: u8, input: &str) {
+  let answers = Answers::parse(manifest).unwrap_or_else(|err| panic!("invalid manifest: {err}"));
+  let parsed = crate::parse_normalized::<S>(input, Mode::Strict)
+    .unwrap_or_else(|err| panic!("failed to parse day {day}: {err}"));
+
+  for (part, answer) in [
+    (Part::One, S::part_one(&parsed).map(Into::into)),
+    (Part::Two, S::part_two(&parsed).map(Into::into)),
+  ] {
+    let answer =
+      answer.unwrap_or_else(|err| panic!("failed to solve day {day}, part {part}: {err}"));
+
+    match answers.get(day, part) {
+      | Some(expected) => assert_eq!(&answer, expected, "wrong answer to day {day}, part {part}"),
+      | None => panic!("no answer to day {day}, part {part} in the manifest, got `{answer}`"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const MANIFEST: &str =
+    "[day_10]\npart_1 = \"15360\"\npart_2 = '''\n##..\n#..#'''\n\n[day_9]\npart_1 = \"6057\"\n";
+
+  #[test]
+  fn test_parse() {
+    let answers = Answers::parse(MANIFEST).unwrap();
+
+    assert_eq!(answers.get(9, Part::One), Some(&Answer::Integer(6057)));
+    assert_eq!(answers.get(9, Part::Two), None);
+    assert_eq!(
+      answers.get(10, Part::Two),
+      Some(&Answer::parse("##..\n#..#"))
+    );
+
+    assert_eq!(
+      Answers::parse("[day_1]\npart_1 = 1").map(|answers| answers.get(1, Part::One).cloned()),
+      Ok(Some(Answer::Integer(1)))
+    );
+
+    assert!(Answers::parse("[day_x]\npart_1 = \"1\"").is_err());
+    assert!(Answers::parse("[day_1]\npart_3 = \"1\"").is_err());
+    assert!(Answers::parse("[day_1]\npart_1 = 1.5").is_err());
+  }
+
+  #[test]
+  fn test_verdict() {
+    let answers = Answers::parse(MANIFEST).unwrap();
+
+    assert_eq!(
+      answers.verdict(9, Part::One, &Answer::from(6057usize)),
+      Verdict::Correct
+    );
+    assert_eq!(
+      answers.verdict(9, Part::One, &Answer::from(6058usize)),
+      Verdict::Wrong {
+        expected: Answer::Integer(6057)
+      }
+    );
+    assert_eq!(
+      answers.verdict(9, Part::Two, &Answer::from(2514usize)),
+      Verdict::Unknown
+    );
+  }
+
+  #[test]
+  fn test_roundtrip() {
+    let answers = Answers::parse(MANIFEST).unwrap();
+
+    assert_eq!(Answers::parse(&answers.to_string()), Ok(answers));
+  }
+}
@@ -0,0 +1,267 @@
+//! Statistical benchmarking of solutions. Every phase is run repeatedly after a few warm-up runs,
+//! and the medians are compared against a baseline file kept between runs.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::report::timed;
+use crate::{Part, Result, Solution};
+
+/// Phase of a solution being benchmarked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+  Parse,
+  Part(Part),
+}
+
+impl Phase {
+  /// Key used in baseline files.
+  fn key(&self) -> &'static str {
+    match self {
+      | Phase::Parse => "parse",
+      | Phase::Part(Part::One) => "part_1",
+      | Phase::Part(Part::Two) => "part_2",
+    }
+  }
+
+  fn from_key(key: &str) -> Option<Self> {
+    match key {
+      | "parse" => Some(Phase::Parse),
+      | "part_1" => Some(Phase::Part(Part::One)),
+      | "part_2" => Some(Phase::Part(Part::Two)),
+      | _ => None,
+    }
+  }
+}
+
+impl Display for Phase {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      | Phase::Parse => write!(f, "Parse"),
+      | Phase::Part(part) => write!(f, "Part {part}"),
+    }
+  }
+}
+
+/// Summary statistics over a set of samples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+  pub min: Duration,
+  pub median: Duration,
+  pub mean: Duration,
+  pub stddev: Duration,
+}
+
+impl Stats {
+  /// Computes statistics over the given samples. Returns `None` if there are none.
+  pub fn new(samples: &[Duration]) -> Option<Self> {
+    if samples.is_empty() {
+      return None;
+    }
+
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+
+    let count = sorted.len();
+    let middle = count / 2;
+
+    let median = if count.is_multiple_of(2) {
+      (sorted[middle - 1] + sorted[middle]) / 2
+    } else {
+      sorted[middle]
+    };
+
+    let mean = sorted.iter().sum::<Duration>() / count as u32;
+
+    // Sample standard deviation, zero for a single sample.
+    let variance = if count > 1 {
+      sorted
+        .iter()
+        .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+        .sum::<f64>()
+        / (count - 1) as f64
+    } else {
+      0.0
+    };
+
+    Some(Self {
+      min: sorted[0],
+      median,
+      mean,
+      stddev: Duration::from_secs_f64(variance.sqrt()),
+    })
+  }
+}
+
+/// How many times to run each phase.
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+  /// Runs discarded before measuring.
+  pub warmup: usize,
+  /// Measured runs, at least one.
+  pub samples: usize,
+}
+
+/// Benchmark results of a single puzzle.
+#[derive(Clone, Debug)]
+pub struct Bench {
+  pub title: &'static str,
+  pub phases: Vec<(Phase, Stats)>,
+}
+
+impl Solution {
+  /// Benchmarks parsing and the given parts. Parts are solved over a single parsed input, so their
+  /// timings do not include parsing. Fails without measuring anything if any phase fails.
+  pub fn bench(&self, input: &str, parts: &[Part], config: Config) -> Result<Bench> {
+    let runs = config.warmup + config.samples.max(1);
+
+    let measure = |run: &dyn Fn()| {
+      let samples = (0..runs)
+        .map(|_| timed(run).1)
+        .skip(config.warmup)
+        .collect::<Vec<_>>();
+
+      Stats::new(&samples).expect("there should be at least one sample")
+    };
+
+    let parsed = self.parse(input)?;
+
+    for &part in parts {
+      self.solve(&parsed, part)?;
+    }
+
+    let mut phases = vec![(Phase::Parse, measure(&|| drop(self.parse(input))))];
+
+    for &part in parts {
+      phases.push((
+        Phase::Part(part),
+        measure(&|| drop(self.solve(&parsed, part))),
+      ));
+    }
+
+    Ok(Bench {
+      title: self.title,
+      phases,
+    })
+  }
+}
+
+/// Medians recorded by earlier runs, keyed by year, day and phase. Stored as plain text, one
+/// `<year> <day> <phase> <nanoseconds>` entry per line.
+#[derive(Debug, Default)]
+pub struct Baseline {
+  path: PathBuf,
+  entries: BTreeMap<(u16, u8, Phase), Duration>,
+}
+
+impl Baseline {
+  /// Loads the baseline from the given file. A missing file yields an empty baseline.
+  pub fn load(path: &Path) -> io::Result<Self> {
+    let contents = match fs::read_to_string(path) {
+      | Ok(contents) => contents,
+      | Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+      | Err(err) => return Err(err),
+    };
+
+    let entries = contents
+      .lines()
+      .enumerate()
+      .filter(|(_, line)| !line.trim().is_empty())
+      .map(|(index, line)| {
+        Self::parse_entry(line).ok_or_else(|| {
+          io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("malformed baseline entry on line {}: `{line}`", index + 1),
+          )
+        })
+      })
+      .collect::<io::Result<_>>()?;
+
+    Ok(Self {
+      path: path.to_path_buf(),
+      entries,
+    })
+  }
+
+  fn parse_entry(line: &str) -> Option<((u16, u8, Phase), Duration)> {
+    let mut fields = line.split_whitespace();
+
+    let year = fields.next()?.parse().ok()?;
+    let day = fields.next()?.parse().ok()?;
+    let phase = Phase::from_key(fields.next()?)?;
+    let nanos = fields.next()?.parse().ok()?;
+
+    fields
+      .next()
+      .is_none()
+      .then_some(((year, day, phase), Duration::from_nanos(nanos)))
+  }
+
+  pub fn get(&self, year: u16, day: u8, phase: Phase) -> Option<Duration> {
+    self.entries.get(&(year, day, phase)).copied()
+  }
+
+  pub fn insert(&mut self, year: u16, day: u8, phase: Phase, median: Duration) {
+    self.entries.insert((year, day, phase), median);
+  }
+
+  pub fn save(&self) -> io::Result<()> {
+    let contents = self
+      .entries
+      .iter()
+      .map(|((year, day, phase), median)| {
+        format!(
+          "{year} {day} {phase} {nanos}\n",
+          phase = phase.key(),
+          nanos = median.as_nanos()
+        )
+      })
+      .collect::<String>();
+
+    fs::write(&self.path, contents)
+  }
+}
+
+/// Relative change of `median` against `baseline`, e.g. `0.25` for 25% slower.
+pub fn change(median: Duration, baseline: Duration) -> f64 {
+  median.as_secs_f64() / baseline.as_secs_f64().max(f64::MIN_POSITIVE) - 1.0
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_stats() {
+    let samples = [4, 1, 3, 2].map(Duration::from_millis);
+    let stats = Stats::new(&samples).unwrap();
+
+    assert_eq!(stats.min, Duration::from_millis(1));
+    assert_eq!(stats.median, Duration::from_micros(2500));
+    assert_eq!(stats.mean, Duration::from_micros(2500));
+    assert_eq!(stats.stddev.as_micros(), 1290);
+
+    assert!(Stats::new(&[]).is_none());
+  }
+
+  #[test]
+  fn test_baseline() {
+    let entry = Baseline::parse_entry("2022 9 part_2 5970000");
+
+    assert_eq!(
+      entry,
+      Some((
+        (2022, 9, Phase::Part(Part::Two)),
+        Duration::from_micros(5970)
+      ))
+    );
+
+    assert!(Baseline::parse_entry("2022 9 part_3 5970000").is_none());
+    assert!(Baseline::parse_entry("2022 9 parse").is_none());
+    assert!(Baseline::parse_entry("2022 9 parse 1 2").is_none());
+  }
+}
@@ -0,0 +1,648 @@
+//! Command-line interfaces of the year binaries and of the workspace-wide runner.
+
+use std::io;
+use std::num::NonZeroUsize;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::answers::Answers;
+use crate::bench::{self, Baseline, Config};
+use crate::input::{self, Source};
+use crate::parallel::{self, Task};
+use crate::report::{self, Report};
+use crate::{guard, Mode, Part, Puzzle, Year};
+
+/// Options shared by both interfaces.
+#[derive(Args)]
+struct Options {
+  /// Part to run: `1` or `2`. Runs both parts if omitted.
+  #[arg(short, long)]
+  part: Option<Part>,
+
+  /// Input file to solve instead of the default one, `-` reads from stdin. Requires a single day
+  /// to be selected, which also solves piped stdin without this option.
+  #[arg(short, long)]
+  input: Option<PathBuf>,
+
+  /// Directory with inputs laid out as `<year>/day_<day>.txt`.
+  #[arg(long, env = "AOC_INPUTS")]
+  inputs: Option<PathBuf>,
+
+  /// Skip malformed lines of input instead of failing, where a solution supports it. Answers to
+  /// such inputs are likely wrong.
+  #[arg(long)]
+  lenient: bool,
+
+  /// Time limit of each phase, e.g. `10s` or `500ms`. A part running longer is reported as timed
+  /// out and abandoned, so the rest of the run goes on.
+  #[arg(long, env = "AOC_TIMEOUT", value_parser = timeout, conflicts_with = "bench")]
+  timeout: Option<Duration>,
+
+  /// Solve days concurrently on the given number of threads, or on every core if the number is
+  /// omitted. Results are reported in the usual order, but timings are skewed by the days running
+  /// alongside.
+  #[arg(short, long, num_args = 0..=1, conflicts_with = "bench")]
+  jobs: Option<Option<NonZeroUsize>>,
+
+  /// Solve the parts of a day concurrently as well. Each part parses the input on its own.
+  #[arg(long, requires = "jobs")]
+  parallel_parts: bool,
+
+  /// Output format. Machine-readable formats are printed once every puzzle has been run.
+  #[arg(short, long, value_enum, default_value_t = Format::Text, conflicts_with = "bench")]
+  format: Format,
+
+  #[command(flatten)]
+  bench: BenchOptions,
+}
+
+/// Output formats of the run results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+  /// Human-readable list of answers and timings.
+  Text,
+  /// JSON array with a record per puzzle.
+  Json,
+  /// CSV table with a row per puzzle.
+  Csv,
+  /// Markdown table with a row per puzzle.
+  Markdown,
+}
+
+/// Options of the benchmark mode.
+#[derive(Args)]
+#[command(next_help_heading = "Benchmark")]
+struct BenchOptions {
+  /// Benchmark the selected puzzles instead of running them once.
+  #[arg(long)]
+  bench: bool,
+
+  /// Runs discarded before measuring each phase.
+  #[arg(long, default_value_t = 3, requires = "bench")]
+  warmup: usize,
+
+  /// Measured runs of each phase.
+  #[arg(long, default_value_t = 20, requires = "bench")]
+  samples: usize,
+
+  /// File with medians of earlier runs to compare against. Missing entries are recorded into it.
+  #[arg(long, default_value = "aoc-bench.txt", requires = "bench")]
+  baseline: PathBuf,
+
+  /// Overwrite the baseline with the medians of this run.
+  #[arg(long, requires = "bench")]
+  save_baseline: bool,
+
+  /// Slowdown against the baseline, in percent, reported as a regression.
+  #[arg(long, default_value_t = 10.0, requires = "bench")]
+  threshold: f64,
+}
+
+#[derive(Parser)]
+#[command(about = "Runs Advent of Code solutions for a single year", long_about = None)]
+struct YearArgs {
+  /// Days to run: a single day, an inclusive range or a comma-separated list, e.g. `12`, `3-7`,
+  /// `10-` or `1,3,5-7`.
+  #[arg(short, long)]
+  day: Option<Days>,
+
+  /// List the selected days instead of running them.
+  #[arg(short, long)]
+  list: bool,
+
+  #[command(flatten)]
+  options: Options,
+}
+
+#[derive(Parser)]
+#[command(about = "Runs Advent of Code solutions across all years", long_about = None)]
+struct RunnerArgs {
+  #[command(subcommand)]
+  command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+  /// Runs a single day, a whole year or everything.
+  Run {
+    /// Event year, e.g. `2022`.
+    #[arg(required_unless_present = "all")]
+    year: Option<u16>,
+
+    /// Days to run, same syntax as `--day` of the year binaries, e.g. `9` or `3-7`.
+    days: Option<Days>,
+
+    /// Run every registered year.
+    #[arg(short, long, conflicts_with_all = ["year", "days"])]
+    all: bool,
+
+    #[command(flatten)]
+    options: Options,
+  },
+  /// Lists registered years and days.
+  List {
+    /// Event year, e.g. `2022`. Lists every year if omitted.
+    year: Option<u16>,
+  },
+}
+
+/// Selection of days, made of inclusive ranges.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Days(Vec<RangeInclusive<u8>>);
+
+impl Days {
+  pub fn contains(&self, day: u8) -> bool {
+    self.0.iter().any(|range| range.contains(&day))
+  }
+}
+
+impl FromStr for Days {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn day(s: &str) -> Result<u8, String> {
+      s.trim()
+        .parse::<u8>()
+        .map_err(|_| format!("invalid day `{s}`"))
+    }
+
+    s.split(',')
+      .map(|chunk| {
+        match chunk.split_once('-') {
+          | Some((start, "")) => Ok(day(start)?..=u8::MAX),
+          | Some(("", end)) => Ok(1..=day(end)?),
+          | Some((start, end)) => Ok(day(start)?..=day(end)?),
+          | None => day(chunk).map(|day| day..=day),
+        }
+      })
+      .collect::<Result<Vec<_>, _>>()
+      .map(Days)
+  }
+}
+
+/// Parses a time limit: a number of seconds, optionally with an `ms`, `s` or `m` suffix.
+fn timeout(s: &str) -> Result<Duration, String> {
+  let (value, unit) = match s.find(|ch: char| ch.is_ascii_alphabetic()) {
+    | Some(index) => s.split_at(index),
+    | None => (s, "s"),
+  };
+
+  let value = value
+    .trim()
+    .parse::<f64>()
+    .ok()
+    .filter(|value| value.is_finite() && *value > 0.0)
+    .ok_or_else(|| format!("invalid time limit `{s}`"))?;
+
+  let secs = match unit {
+    | "ms" => value / 1000.0,
+    | "s" => value,
+    | "m" => value * 60.0,
+    | _ => return Err(format!("unknown unit `{unit}`, expected `ms`, `s` or `m`")),
+  };
+
+  Duration::try_from_secs_f64(secs).map_err(|err| format!("invalid time limit `{s}`: {err}"))
+}
+
+/// A selected puzzle along with its input.
+struct Job<'y> {
+  year: &'y Year,
+  puzzle: Puzzle,
+  source: Source,
+  input: io::Result<String>,
+  bundled: bool,
+}
+
+/// Puzzles picked for a run, grouped by year.
+type Selection<'y> = Vec<(&'y Year, Vec<&'y Puzzle>)>;
+
+fn select<'y>(years: &'y [Year], year: Option<u16>, days: Option<&Days>) -> Selection<'y> {
+  years
+    .iter()
+    .filter(|candidate| year.is_none_or(|year| candidate.year == year))
+    .map(|year| {
+      let puzzles = year
+        .puzzles
+        .iter()
+        .filter(|puzzle| days.is_none_or(|days| days.contains(puzzle.day)))
+        .collect::<Vec<_>>();
+
+      (year, puzzles)
+    })
+    .filter(|(_, puzzles)| !puzzles.is_empty())
+    .collect()
+}
+
+fn list(selection: &Selection, with_headers: bool) {
+  for (year, puzzles) in selection {
+    if with_headers {
+      println!("# {year}\n", year = year.year);
+    }
+
+    for puzzle in puzzles {
+      println!("{title}", title = puzzle.solution.title());
+    }
+
+    if with_headers {
+      println!();
+    }
+  }
+}
+
+/// Benchmarks a single puzzle, prints the statistics and compares the medians against the
+/// baseline. Returns the number of phases that regressed, or `None` if the puzzle failed.
+fn bench(
+  year: u16,
+  puzzle: &Puzzle,
+  input: &str,
+  parts: &[Part],
+  options: &BenchOptions,
+  baseline: &mut Baseline,
+) -> Option<usize> {
+  let config = Config {
+    warmup: options.warmup,
+    samples: options.samples,
+  };
+
+  let title = puzzle.solution.title();
+
+  let result = match puzzle.solution.bench(input, parts, config) {
+    | Ok(result) => result,
+    | Err(err) => {
+      println!("{title}\n\n- Failed: {err}\n");
+      return None;
+    },
+  };
+
+  let mut regressions = 0;
+
+  println!("{title}\n");
+
+  for (phase, stats) in &result.phases {
+    print!(
+      "- {phase}: median {median:.2?}, mean {mean:.2?} ± {stddev:.2?}, min {min:.2?}",
+      median = stats.median,
+      mean = stats.mean,
+      stddev = stats.stddev,
+      min = stats.min,
+    );
+
+    match baseline.get(year, puzzle.day, *phase) {
+      | Some(recorded) => {
+        let change = bench::change(stats.median, recorded) * 100.0;
+        let regressed = change > options.threshold;
+
+        print!(", baseline {recorded:.2?} ({change:+.1}%)");
+
+        if regressed {
+          regressions += 1;
+          print!(" REGRESSION");
+        }
+
+        if options.save_baseline {
+          baseline.insert(year, puzzle.day, *phase, stats.median);
+        }
+      },
+      | None => baseline.insert(year, puzzle.day, *phase, stats.median),
+    }
+
+    println!();
+  }
+
+  println!();
+
+  Some(regressions)
+}
+
+/// Runs or benchmarks the selected puzzles. Returns the number of puzzles that failed, including
+/// those whose input couldn't be read.
+fn execute(selection: &Selection, options: &Options, with_headers: bool) -> usize {
+  let selected = selection
+    .iter()
+    .map(|(_, puzzles)| puzzles.len())
+    .sum::<usize>();
+
+  if options.input.is_some() && selected > 1 {
+    eprintln!("The --input option requires a single day to be selected.");
+    return selected;
+  }
+
+  // Piped stdin is solved instead of the default input when a single day is selected, e.g.
+  // `cat input.txt | aoc_2022 --day 9`.
+  let piped = options.input.is_none() && input::piped();
+
+  if piped && selected > 1 {
+    eprintln!("Piped input requires a single day to be selected, so it's ignored.\n");
+  }
+
+  let parts = match options.part {
+    | Some(part) => vec![part],
+    | None => Part::ALL.to_vec(),
+  };
+
+  let mut baseline = if options.bench.bench {
+    match Baseline::load(&options.bench.baseline) {
+      | Ok(baseline) => Some(baseline),
+      | Err(err) => {
+        eprintln!(
+          "Failed to read the benchmark baseline from {path}: {err}",
+          path = options.bench.baseline.display()
+        );
+        return selected;
+      },
+    }
+  } else {
+    None
+  };
+
+  let mode = if options.lenient {
+    Mode::Lenient
+  } else {
+    Mode::Strict
+  };
+
+  // Panics are reported along with the rest of the results instead of being printed on the spot.
+  guard::install();
+
+  // Inputs are read up front, so that puzzles can be solved on a thread pool.
+  let jobs = selection
+    .iter()
+    .flat_map(|&(year, ref puzzles)| puzzles.iter().map(move |&&puzzle| (year, puzzle)))
+    .map(|(year, puzzle)| {
+      let puzzle = Puzzle {
+        solution: puzzle.solution.with_mode(mode),
+        ..puzzle
+      };
+
+      let source = Source::resolve(
+        year.year,
+        puzzle.day,
+        &puzzle.input,
+        options.input.as_deref(),
+        piped && selected == 1,
+        options.inputs.as_deref(),
+      );
+
+      // Reading stdin blocks until it's closed, so say what is being waited for.
+      if source == Source::Stdin {
+        eprintln!(
+          "Reading the input of {title} from stdin...",
+          title = puzzle.solution.title()
+        );
+      }
+
+      let input = source.read();
+
+      // Known answers only apply to the bundled inputs.
+      let bundled = source == puzzle.input.source();
+
+      Job {
+        year,
+        puzzle,
+        source,
+        input,
+        bundled,
+      }
+    })
+    .collect::<Vec<_>>();
+
+  let tasks = jobs
+    .iter()
+    .enumerate()
+    .filter_map(|(index, job)| {
+      let task = Task {
+        year: job.year.year,
+        puzzle: job.puzzle,
+        input: job.input.as_deref().ok()?,
+      };
+
+      Some((index, task))
+    })
+    .collect::<Vec<_>>();
+
+  let text = options.format == Format::Text;
+  let mut reports = Vec::<Report>::new();
+  let mut regressions = 0;
+  let mut failures = 0;
+
+  thread::scope(|scope| {
+    let mut solved = options.jobs.map(|threads| {
+      let threads = threads
+        .or_else(|| thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get);
+
+      parallel::solve(
+        scope,
+        &tasks,
+        &parts,
+        threads,
+        options.parallel_parts,
+        options.timeout,
+      )
+    });
+
+    let mut jobs = jobs.iter().enumerate();
+
+    for (year, puzzles) in selection {
+      if with_headers && text {
+        println!("# {year}\n", year = year.year);
+      }
+
+      let mut total = Duration::ZERO;
+
+      let answers = year.answers().unwrap_or_else(|err| {
+        eprintln!(
+          "Answers of {year} are not checked: {err}\n",
+          year = year.year
+        );
+        Answers::default()
+      });
+
+      for (index, job) in jobs.by_ref().take(puzzles.len()) {
+        let puzzle = &job.puzzle;
+
+        match &job.input {
+          | Ok(input) => {
+            if let Some(baseline) = baseline.as_mut() {
+              match bench(year.year, puzzle, input, &parts, &options.bench, baseline) {
+                | Some(regressed) => regressions += regressed,
+                | None => failures += 1,
+              }
+            } else {
+              let mut report = match solved.as_mut() {
+                | Some(solved) => solved.get(index),
+                | None => puzzle.execute(year.year, input, &parts, options.timeout),
+              };
+
+              if job.bundled {
+                report.verify(&answers);
+              }
+
+              total += report.total();
+
+              if report.failed() {
+                failures += 1;
+              }
+
+              if text {
+                println!("{report}");
+              } else {
+                reports.push(report);
+              }
+            }
+          },
+          | Err(err) => {
+            eprintln!(
+              "{title}\n\n- Failed to read input from {source}: {err}\n",
+              title = puzzle.solution.title(),
+              source = job.source
+            );
+
+            failures += 1;
+          },
+        }
+      }
+
+      if text && baseline.is_none() {
+        println!("Total: {total:.2?}\n");
+      }
+    }
+  });
+
+  match options.format {
+    | Format::Text => {},
+    | Format::Json => println!("{}", report::json(&reports)),
+    | Format::Csv => print!("{}", report::csv(&reports)),
+    | Format::Markdown => print!("{}", report::markdown(&reports)),
+  }
+
+  if let Some(baseline) = baseline {
+    if regressions > 0 {
+      println!(
+        "{regressions} phase(s) regressed by more than {threshold}% against the baseline.",
+        threshold = options.bench.threshold
+      );
+    }
+
+    if let Err(err) = baseline.save() {
+      eprintln!(
+        "Failed to write the benchmark baseline to {path}: {err}",
+        path = options.bench.baseline.display()
+      );
+    }
+  }
+
+  if failures > 0 {
+    eprintln!("{failures} of {selected} puzzle(s) failed.");
+  }
+
+  failures
+}
+
+/// Exit code of a run: failure if any of the puzzles failed.
+fn exit_code(failures: usize) -> ExitCode {
+  if failures > 0 {
+    ExitCode::FAILURE
+  } else {
+    ExitCode::SUCCESS
+  }
+}
+
+/// Entry point of the year binaries: parses command-line arguments and runs or lists the selected
+/// puzzles of the given year. Exits with a failure if any of the puzzles failed.
+pub fn run(year: Year) -> ExitCode {
+  let args = YearArgs::parse();
+  let years = [year];
+  let selection = select(&years, None, args.day.as_ref());
+
+  if selection.is_empty() {
+    eprintln!("No solutions match the selected days.");
+    ExitCode::FAILURE
+  } else if args.list {
+    list(&selection, false);
+    ExitCode::SUCCESS
+  } else {
+    exit_code(execute(&selection, &args.options, false))
+  }
+}
+
+/// Entry point of the workspace-wide runner: parses command-line arguments and runs or lists the
+/// selected puzzles across all given years. Exits with a failure if any of the puzzles failed.
+pub fn run_workspace(years: Vec<Year>) -> ExitCode {
+  let args = RunnerArgs::parse();
+
+  match args.command {
+    | Command::Run {
+      year,
+      days,
+      options,
+      ..
+    } => {
+      let selection = select(&years, year, days.as_ref());
+
+      if selection.is_empty() {
+        eprintln!("No solutions match the selected year and days.");
+        ExitCode::FAILURE
+      } else {
+        exit_code(execute(&selection, &options, true))
+      }
+    },
+    | Command::List { year } => {
+      let selection = select(&years, year, None);
+
+      if selection.is_empty() {
+        eprintln!("No solutions match the selected year.");
+        ExitCode::FAILURE
+      } else {
+        list(&selection, true);
+        ExitCode::SUCCESS
+      }
+    },
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_days() {
+    let days = "1,3,5-7,20-".parse::<Days>().unwrap();
+
+    assert!(days.contains(1));
+    assert!(!days.contains(2));
+    assert!(days.contains(3));
+    assert!(days.contains(6));
+    assert!(!days.contains(8));
+    assert!(days.contains(25));
+
+    assert!("x".parse::<Days>().is_err());
+    assert!("1-y".parse::<Days>().is_err());
+  }
+
+  #[test]
+  fn test_timeout() {
+    assert_eq!(timeout("10"), Ok(Duration::from_secs(10)));
+    assert_eq!(timeout("1.5s"), Ok(Duration::from_millis(1500)));
+    assert_eq!(timeout("250ms"), Ok(Duration::from_millis(250)));
+    assert_eq!(timeout("2m"), Ok(Duration::from_secs(120)));
+    assert!(timeout("0").is_err());
+    assert!(timeout("-1s").is_err());
+    assert!(timeout("1h").is_err());
+    assert!(timeout("99999999999999999999999").is_err());
+    assert!(timeout("99999999999999999999m").is_err());
+  }
+
+  #[test]
+  fn test_part() {
+    assert_eq!("1".parse::<Part>(), Ok(Part::One));
+    assert_eq!("two".parse::<Part>(), Ok(Part::Two));
+    assert!("3".parse::<Part>().is_err());
+  }
+}
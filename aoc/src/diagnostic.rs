@@ -0,0 +1,248 @@
+//! Annotated snippets of malformed input. A [Snippet] shows the offending line with the span
+//! underlined and the tokens that were expected there, and is attached to parse errors via
+//! [Error::with_snippet]. Errors of chumsky parsers are converted with [Error::from_chumsky], while
+//! hand-written parsers point at a piece of a line with [Error::at]. Errors raised while parsing a
+//! slice of the input are moved onto the whole input with [Error::within].
+
+use std::fmt::{self, Display};
+use std::ops::Range;
+
+use chumsky::error::{Simple, SimpleReason};
+
+use crate::{Error, Location};
+
+/// A line of input with the offending span underlined.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snippet {
+  /// Char index of the span in the input the snippet was made of.
+  start: usize,
+  location: Location,
+  source: String,
+  width: usize,
+  expected: Vec<String>,
+}
+
+impl Snippet {
+  /// Points at a span of the input. Spans are char indices, which is what chumsky spans over `&str`
+  /// are. Spans running past the end of their line are cut at the line end, and empty spans (e.g.
+  /// at the end of input) still underline a single character.
+  pub fn new(input: &str, span: Range<usize>) -> Self {
+    let location = Location::of(input, span.start);
+
+    let source = input
+      .lines()
+      .nth(location.line - 1)
+      .unwrap_or_default()
+      .to_string();
+
+    let rest = source.chars().count().saturating_sub(location.column - 1);
+
+    Self {
+      start: span.start,
+      location,
+      source,
+      width: span.len().min(rest).max(1),
+      expected: Vec::new(),
+    }
+  }
+
+  /// Points at `part`, which must be a slice of `input`, e.g. a token split off a line.
+  pub fn of(input: &str, part: &str) -> Self {
+    let start = offset(input, part);
+
+    Self::new(input, start..start + part.chars().count())
+  }
+
+  /// Lists the tokens expected at the span, e.g. `` `|` `` or `a number`.
+  pub fn expected<I>(mut self, expected: I) -> Self
+  where
+    I: IntoIterator,
+    I::Item: Display,
+  {
+    self.expected = expected
+      .into_iter()
+      .map(|token| token.to_string())
+      .collect();
+    self
+  }
+
+  pub fn location(&self) -> Location {
+    self.location
+  }
+
+  /// Moves the snippet to the given line, for line-by-line parsers.
+  pub(crate) fn on_line(mut self, line: usize) -> Self {
+    self.location.line = line;
+    self
+  }
+
+  /// Points at the same span, but within `input`, of which `part` is the slice the snippet was made
+  /// of.
+  fn within(self, input: &str, part: &str) -> Self {
+    let start = offset(input, part) + self.start;
+
+    Self::new(input, start..start + self.width).expected(self.expected)
+  }
+}
+
+/// Char index of `part` in `input`, of which it must be a slice.
+fn offset(input: &str, part: &str) -> usize {
+  let offset = (part.as_ptr() as usize)
+    .checked_sub(input.as_ptr() as usize)
+    .filter(|offset| offset + part.len() <= input.len())
+    .expect("snippet part should be a slice of the input");
+
+  input[..offset].chars().count()
+}
+
+impl Display for Snippet {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let line = self.location.line.to_string();
+    let gutter = " ".repeat(line.len());
+
+    writeln!(f, "{gutter} |")?;
+    writeln!(f, "{line} | {source}", source = self.source)?;
+    write!(
+      f,
+      "{gutter} | {indent}{underline}",
+      indent = " ".repeat(self.location.column - 1),
+      underline = "^".repeat(self.width)
+    )?;
+
+    match self.expected.as_slice() {
+      | [] => Ok(()),
+      | [token] => write!(f, " expected {token}"),
+      | tokens => write!(f, " expected one of {}", tokens.join(", ")),
+    }
+  }
+}
+
+impl Error {
+  /// Converts errors of a chumsky parser run over `input`. Only the first error is reported, with
+  /// the expected tokens sorted so the output is stable.
+  pub fn from_chumsky(input: &str, errors: Vec<Simple<char>>) -> Self {
+    let Some(err) = errors.into_iter().next() else {
+      return Error::parse("unknown syntax error");
+    };
+
+    let message = match (err.reason(), err.found()) {
+      | (SimpleReason::Custom(message), _) => message.clone(),
+      | (SimpleReason::Unclosed { delimiter, .. }, _) => {
+        format!("unclosed delimiter `{}`", delimiter.escape_debug())
+      },
+      | (SimpleReason::Unexpected, Some(found)) => format!("unexpected `{}`", found.escape_debug()),
+      | (SimpleReason::Unexpected, None) => "unexpected end of input".to_string(),
+    };
+
+    let mut expected = err
+      .expected()
+      .flatten()
+      .map(|token| format!("`{}`", token.escape_debug()))
+      .collect::<Vec<_>>();
+
+    expected.sort();
+
+    if err.expected().any(Option::is_none) {
+      expected.push("end of input".to_string());
+    }
+
+    Error::parse(message).with_snippet(Snippet::new(input, err.span()).expected(expected))
+  }
+
+  /// Parse error pointing at `part`, a slice of `input`, and listing the tokens expected there, see
+  /// [Snippet::of].
+  pub fn at<I>(input: &str, part: &str, message: impl Into<String>, expected: I) -> Self
+  where
+    I: IntoIterator,
+    I::Item: Display,
+  {
+    Error::parse(message).with_snippet(Snippet::of(input, part).expected(expected))
+  }
+
+  /// Moves an error raised while parsing `part`, a slice of `input`, onto the whole `input`, e.g.
+  /// from a single paragraph onto the puzzle input. Only errors with a snippet can be moved, any
+  /// other error is returned as it is.
+  pub fn within(self, input: &str, part: &str) -> Self {
+    match self {
+      | Error::Parse {
+        message,
+        snippet: Some(snippet),
+        ..
+      } => Error::parse(message).with_snippet(snippet.within(input, part)),
+      | err => err,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chumsky::prelude::*;
+
+  use super::*;
+
+  #[test]
+  fn test_snippet() {
+    let input = "move 1 from 2 to 3\nmove x from 2 to 3\n";
+    let line = input.lines().nth(1).unwrap();
+
+    let snippet = Snippet::of(line, &line[5..6]).on_line(2);
+    assert_eq!(snippet.location(), Location::new(2, 6));
+    assert_eq!(
+      snippet.to_string(),
+      "  |\n2 | move x from 2 to 3\n  |      ^"
+    );
+
+    let snippet = Snippet::new(input, 24..100).expected(["a number"]);
+    assert_eq!(snippet.location(), Location::new(2, 6));
+    assert_eq!(
+      snippet.to_string(),
+      "  |\n2 | move x from 2 to 3\n  |      ^^^^^^^^^^^^^ expected a number"
+    );
+  }
+
+  #[test]
+  fn test_within() {
+    let input = "seeds: 1 2\n\nsoil: 3 x 4\n";
+    let paragraph = &input[12..];
+    let line = paragraph.lines().next().unwrap();
+    let values = &line[6..];
+
+    let err = Error::parse("invalid number `x`").with_snippet(Snippet::of(values, &values[2..3]));
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 1, column 3: invalid number `x`"
+    );
+
+    let err = err.within(input, values);
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 3, column 9: invalid number `x`"
+    );
+    assert_eq!(
+      err.snippet().map(Snippet::to_string).as_deref(),
+      Some("  |\n3 | soil: 3 x 4\n  |         ^")
+    );
+
+    let err = Error::parse("incomplete").within(input, values);
+    assert_eq!(err.snippet(), None);
+  }
+
+  #[test]
+  fn test_chumsky() {
+    let parser = text::int::<_, Simple<char>>(10)
+      .separated_by(just(','))
+      .then_ignore(end());
+
+    let input = "1,2\n3";
+    let err = Error::from_chumsky(input, parser.parse(input).unwrap_err());
+
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 1, column 4: unexpected `\\n`"
+    );
+    assert_eq!(
+      err.snippet().map(Snippet::to_string).as_deref(),
+      Some("  |\n1 | 1,2\n  |    ^ expected one of `,`, end of input")
+    );
+  }
+}
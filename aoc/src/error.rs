@@ -0,0 +1,195 @@
+//! Errors shared by all solvers.
+
+use std::fmt::{self, Display};
+use std::num::{ParseIntError, TryFromIntError};
+use std::time::Duration;
+
+use crate::diagnostic::Snippet;
+
+/// 1-based position of a character in the puzzle input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Location {
+  pub line: usize,
+  pub column: usize,
+}
+
+impl Location {
+  pub fn new(line: usize, column: usize) -> Self {
+    Self { line, column }
+  }
+
+  /// Finds the location of the character at the given index. Indices are counted in chars rather
+  /// than bytes, which is what chumsky spans over `&str` are.
+  pub fn of(input: &str, index: usize) -> Self {
+    input
+      .chars()
+      .take(index)
+      .fold(Self::new(1, 1), |location, ch| {
+        if ch == '\n' {
+          Self::new(location.line + 1, 1)
+        } else {
+          Self::new(location.line, location.column + 1)
+        }
+      })
+  }
+}
+
+impl Display for Location {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "line {}, column {}", self.line, self.column)
+  }
+}
+
+/// Why a puzzle could not be solved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+  /// The input doesn't match the expected format, optionally pointing at the offending character
+  /// and showing it in a snippet of the input.
+  Parse {
+    message: String,
+    location: Option<Location>,
+    snippet: Option<Box<Snippet>>,
+  },
+  /// The input is well-formed, but has no answer, e.g. the goal can't be reached.
+  NoSolution(String),
+  /// The solver panicked, e.g. on an unexpected `unwrap`. Holds the panic message.
+  Panic(String),
+  /// The phase didn't finish within the time limit and was abandoned.
+  Timeout(Duration),
+}
+
+impl Error {
+  pub fn parse(message: impl Into<String>) -> Self {
+    Error::Parse {
+      message: message.into(),
+      location: None,
+      snippet: None,
+    }
+  }
+
+  pub fn parse_at(location: Location, message: impl Into<String>) -> Self {
+    Error::Parse {
+      message: message.into(),
+      location: Some(location),
+      snippet: None,
+    }
+  }
+
+  pub fn no_solution(message: impl Into<String>) -> Self {
+    Error::NoSolution(message.into())
+  }
+
+  /// Attaches a snippet of the offending input to a parse error, pointing the error at it.
+  pub fn with_snippet(self, snippet: Snippet) -> Self {
+    match self {
+      | Error::Parse { message, .. } => {
+        Error::Parse {
+          message,
+          location: Some(snippet.location()),
+          snippet: Some(Box::new(snippet)),
+        }
+      },
+      | err => err,
+    }
+  }
+
+  /// Moves a parse error to the given line, for line-by-line parsers that only know the column.
+  /// Errors without a location point at the start of the line.
+  pub fn on_line(self, line: usize) -> Self {
+    match self {
+      | Error::Parse {
+        message,
+        location,
+        snippet,
+      } => {
+        let column = location.map_or(1, |location| location.column);
+
+        Error::Parse {
+          message,
+          location: Some(Location::new(line, column)),
+          snippet: snippet.map(|snippet| Box::new(snippet.on_line(line))),
+        }
+      },
+      | err => err,
+    }
+  }
+
+  /// Snippet of the offending input, see [Error::with_snippet].
+  pub fn snippet(&self) -> Option<&Snippet> {
+    match self {
+      | Error::Parse { snippet, .. } => snippet.as_deref(),
+      | _ => None,
+    }
+  }
+}
+
+impl Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      | Error::Parse {
+        message,
+        location: None,
+        ..
+      } => write!(f, "invalid input: {message}"),
+      | Error::Parse {
+        message,
+        location: Some(location),
+        ..
+      } => write!(f, "invalid input at {location}: {message}"),
+      | Error::NoSolution(message) => write!(f, "no solution: {message}"),
+      | Error::Panic(message) => write!(f, "panicked: {message}"),
+      | Error::Timeout(limit) => write!(f, "timed out after {limit:.2?}"),
+    }
+  }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseIntError> for Error {
+  fn from(err: ParseIntError) -> Self {
+    Error::parse(err.to_string())
+  }
+}
+
+impl From<TryFromIntError> for Error {
+  fn from(err: TryFromIntError) -> Self {
+    Error::parse(err.to_string())
+  }
+}
+
+/// Result of the solver phases.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_location() {
+    let input = "ab\ncd\n\nef";
+
+    assert_eq!(Location::of(input, 0), Location::new(1, 1));
+    assert_eq!(Location::of(input, 1), Location::new(1, 2));
+    assert_eq!(Location::of(input, 3), Location::new(2, 1));
+    assert_eq!(Location::of(input, 7), Location::new(4, 1));
+    assert_eq!(Location::of(input, 8), Location::new(4, 2));
+  }
+
+  #[test]
+  fn test_display() {
+    let err = Error::parse_at(Location::of("1\n2x", 3), "expected a digit");
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 2, column 2: expected a digit"
+    );
+
+    let err = Error::parse("expected a digit").on_line(3);
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 3, column 1: expected a digit"
+    );
+
+    let err = Error::no_solution("no path").on_line(3);
+    assert_eq!(err.to_string(), "no solution: no path");
+  }
+}
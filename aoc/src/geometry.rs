@@ -0,0 +1,310 @@
+//! Points, vectors and directions on the plane. Coordinates follow the screen: `x` grows to the
+//! right and `y` grows downwards, so that a [Point] with non-negative coordinates is a grid
+//! [Position] of `(y, x)` and North points towards row 0.
+
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::grid::{Offset, Position};
+use crate::Error;
+
+/// Point on the plane.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+  pub x: i64,
+  pub y: i64,
+}
+
+/// Displacement between points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector {
+  pub x: i64,
+  pub y: i64,
+}
+
+impl Point {
+  pub const ORIGIN: Point = Point::new(0, 0);
+
+  pub const fn new(x: i64, y: i64) -> Self {
+    Self { x, y }
+  }
+
+  /// Number of orthogonal steps to the other point.
+  pub fn manhattan(self, other: Point) -> i64 {
+    (other - self).manhattan()
+  }
+
+  /// Number of steps to the other point when diagonal steps are allowed too.
+  pub fn chebyshev(self, other: Point) -> i64 {
+    (other - self).chebyshev()
+  }
+
+  /// Grid position of the point, if neither coordinate is negative.
+  pub fn to_position(self) -> Option<Position> {
+    Some((usize::try_from(self.y).ok()?, usize::try_from(self.x).ok()?))
+  }
+}
+
+impl From<Position> for Point {
+  fn from((row, column): Position) -> Self {
+    let coordinate = |value: usize| i64::try_from(value).expect("grid positions should fit i64");
+
+    Point::new(coordinate(column), coordinate(row))
+  }
+}
+
+impl Display for Point {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "({}, {})", self.x, self.y)
+  }
+}
+
+impl Vector {
+  pub const ZERO: Vector = Vector::new(0, 0);
+
+  pub const fn new(x: i64, y: i64) -> Self {
+    Self { x, y }
+  }
+
+  /// Length of the vector when moving orthogonally.
+  pub fn manhattan(self) -> i64 {
+    self.x.abs() + self.y.abs()
+  }
+
+  /// Length of the vector when moving diagonally is allowed too.
+  pub fn chebyshev(self) -> i64 {
+    self.x.abs().max(self.y.abs())
+  }
+
+  /// Single step towards the vector's direction, e.g. `(3, -2)` becomes `(1, -1)`.
+  pub fn signum(self) -> Vector {
+    Vector::new(self.x.signum(), self.y.signum())
+  }
+
+  /// Grid offset of the vector as `(rows, columns)`.
+  pub fn to_offset(self) -> Offset {
+    let component = |value: i64| isize::try_from(value).expect("vectors should fit isize");
+
+    (component(self.y), component(self.x))
+  }
+}
+
+impl Display for Vector {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "<{}, {}>", self.x, self.y)
+  }
+}
+
+impl Add<Vector> for Point {
+  type Output = Point;
+
+  fn add(self, vector: Vector) -> Point {
+    Point::new(self.x + vector.x, self.y + vector.y)
+  }
+}
+
+impl AddAssign<Vector> for Point {
+  fn add_assign(&mut self, vector: Vector) {
+    *self = *self + vector;
+  }
+}
+
+impl Sub<Vector> for Point {
+  type Output = Point;
+
+  fn sub(self, vector: Vector) -> Point {
+    Point::new(self.x - vector.x, self.y - vector.y)
+  }
+}
+
+impl SubAssign<Vector> for Point {
+  fn sub_assign(&mut self, vector: Vector) {
+    *self = *self - vector;
+  }
+}
+
+impl Sub for Point {
+  type Output = Vector;
+
+  fn sub(self, other: Point) -> Vector {
+    Vector::new(self.x - other.x, self.y - other.y)
+  }
+}
+
+impl Add for Vector {
+  type Output = Vector;
+
+  fn add(self, other: Vector) -> Vector {
+    Vector::new(self.x + other.x, self.y + other.y)
+  }
+}
+
+impl Sub for Vector {
+  type Output = Vector;
+
+  fn sub(self, other: Vector) -> Vector {
+    Vector::new(self.x - other.x, self.y - other.y)
+  }
+}
+
+impl Neg for Vector {
+  type Output = Vector;
+
+  fn neg(self) -> Vector {
+    Vector::new(-self.x, -self.y)
+  }
+}
+
+impl Mul<i64> for Vector {
+  type Output = Vector;
+
+  fn mul(self, factor: i64) -> Vector {
+    Vector::new(self.x * factor, self.y * factor)
+  }
+}
+
+/// One of the eight compass directions, ordered clockwise from North.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+  North,
+  NorthEast,
+  East,
+  SouthEast,
+  South,
+  SouthWest,
+  West,
+  NorthWest,
+}
+
+impl Direction {
+  /// All the directions, clockwise from North.
+  pub const ALL: [Direction; 8] = [
+    Direction::North,
+    Direction::NorthEast,
+    Direction::East,
+    Direction::SouthEast,
+    Direction::South,
+    Direction::SouthWest,
+    Direction::West,
+    Direction::NorthWest,
+  ];
+  /// Directions along the axes, clockwise from North.
+  pub const CARDINAL: [Direction; 4] = [
+    Direction::North,
+    Direction::East,
+    Direction::South,
+    Direction::West,
+  ];
+
+  /// Unit step in the direction, diagonal ones moving along both axes.
+  pub fn vector(self) -> Vector {
+    match self {
+      | Direction::North => Vector::new(0, -1),
+      | Direction::NorthEast => Vector::new(1, -1),
+      | Direction::East => Vector::new(1, 0),
+      | Direction::SouthEast => Vector::new(1, 1),
+      | Direction::South => Vector::new(0, 1),
+      | Direction::SouthWest => Vector::new(-1, 1),
+      | Direction::West => Vector::new(-1, 0),
+      | Direction::NorthWest => Vector::new(-1, -1),
+    }
+  }
+
+  /// Grid offset of a step in the direction, see [crate::grid::Grid::step].
+  pub fn offset(self) -> Offset {
+    self.vector().to_offset()
+  }
+
+  pub fn is_diagonal(self) -> bool {
+    !Direction::CARDINAL.contains(&self)
+  }
+
+  /// Turns clockwise by the given number of eighths of a full turn, counter-clockwise if negative.
+  pub fn rotate(self, eighths: i32) -> Direction {
+    Direction::ALL[(self as i32 + eighths).rem_euclid(8) as usize]
+  }
+
+  /// Turns 90 degrees clockwise.
+  pub fn right(self) -> Direction {
+    self.rotate(2)
+  }
+
+  /// Turns 90 degrees counter-clockwise.
+  pub fn left(self) -> Direction {
+    self.rotate(-2)
+  }
+
+  pub fn reverse(self) -> Direction {
+    self.rotate(4)
+  }
+}
+
+/// Parses both relative (`U`, `D`, `L`, `R`) and compass (`N`, `NE`, `E`, ...) notations.
+impl FromStr for Direction {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      | "U" | "N" => Ok(Direction::North),
+      | "NE" => Ok(Direction::NorthEast),
+      | "R" | "E" => Ok(Direction::East),
+      | "SE" => Ok(Direction::SouthEast),
+      | "D" | "S" => Ok(Direction::South),
+      | "SW" => Ok(Direction::SouthWest),
+      | "L" | "W" => Ok(Direction::West),
+      | "NW" => Ok(Direction::NorthWest),
+      | _ => Err(Error::parse(format!("invalid direction `{s}`"))),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::grid::Grid;
+
+  #[test]
+  fn test_arithmetic() {
+    let a = Point::new(1, 2);
+    let b = Point::new(4, -2);
+
+    assert_eq!(b - a, Vector::new(3, -4));
+    assert_eq!(a + (b - a), b);
+    assert_eq!(a.manhattan(b), 7);
+    assert_eq!(a.chebyshev(b), 4);
+    assert_eq!((b - a).signum(), Vector::new(1, -1));
+    assert_eq!(-(b - a) * 2, Vector::new(-6, 8));
+
+    assert_eq!(a.to_position(), Some((2, 1)));
+    assert_eq!(b.to_position(), None);
+    assert_eq!(Point::from((2, 1)), a);
+  }
+
+  #[test]
+  fn test_direction() {
+    assert_eq!("U".parse(), Ok(Direction::North));
+    assert_eq!("W".parse(), Ok(Direction::West));
+    assert_eq!("SE".parse(), Ok(Direction::SouthEast));
+    assert!("X".parse::<Direction>().is_err());
+
+    assert_eq!(Direction::North.right(), Direction::East);
+    assert_eq!(Direction::North.left(), Direction::West);
+    assert_eq!(Direction::West.rotate(3), Direction::NorthEast);
+    assert_eq!(Direction::SouthWest.reverse(), Direction::NorthEast);
+    assert!(Direction::NorthWest.is_diagonal());
+    assert!(Direction::ALL
+      .into_iter()
+      .all(|direction| direction.vector().chebyshev() == 1));
+
+    // Steps on a grid agree with steps on the plane, and never leave it.
+    let grid = Grid::filled(3, 3, ());
+
+    for direction in Direction::ALL {
+      let point = Point::new(1, 1) + direction.vector();
+      assert_eq!(grid.step((1, 1), direction.offset()), point.to_position());
+    }
+
+    assert_eq!(grid.step((0, 0), Direction::North.offset()), None);
+  }
+}
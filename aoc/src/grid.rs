@@ -0,0 +1,321 @@
+//! Rectangular grids of cells, as found in most map-like puzzles. Cells are stored row by row in a
+//! single vector and addressed by [Position], i.e. `(row, column)`, with `(0, 0)` at the top left.
+
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+use crate::diagnostic::Snippet;
+use crate::{Error, Location, Result};
+
+/// Position of a cell as `(row, column)`.
+pub type Position = (usize, usize);
+
+/// Offset between positions as `(rows, columns)`.
+pub type Offset = (isize, isize);
+
+/// Offsets of the cells above, to the left, to the right and below a cell.
+pub const ORTHOGONAL: [Offset; 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Offsets of all the cells around a cell, row by row.
+pub const SURROUNDING: [Offset; 8] = [
+  (-1, -1),
+  (-1, 0),
+  (-1, 1),
+  (0, -1),
+  (0, 1),
+  (1, -1),
+  (1, 0),
+  (1, 1),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+  width: usize,
+  height: usize,
+  cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+  /// Parses a grid with a cell per character and a row per line. Errors of `cell` are pointed at
+  /// the offending character, and rows must all be of the same, non-zero width.
+  pub fn parse(input: &str, mut cell: impl FnMut(char) -> Result<T>) -> Result<Self> {
+    let rows = input
+      .lines()
+      .enumerate()
+      .map(|(row, line)| {
+        line
+          .chars()
+          .enumerate()
+          .map(|(column, c)| {
+            cell(c).map_err(|err| {
+              match err.snippet() {
+                | Some(_) => err,
+                | None => err.with_snippet(Snippet::new(line, column..column + 1)),
+              }
+              .on_line(row + 1)
+            })
+          })
+          .collect::<Result<Vec<_>>>()
+      })
+      .collect::<Result<Vec<_>>>()?;
+
+    Self::from_rows(rows)
+  }
+
+  /// Builds a grid of the given rows, which must all be of the same, non-zero width.
+  pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+    let height = rows.len();
+    let width = rows.first().map_or(0, Vec::len);
+
+    if width == 0 {
+      return Err(Error::parse("expected a non-empty grid"));
+    }
+
+    if let Some((row, cells)) = rows
+      .iter()
+      .enumerate()
+      .find(|(_, cells)| cells.len() != width)
+    {
+      return Err(Error::parse_at(
+        Location::new(row + 1, 1),
+        format!(
+          "expected a row of {width} cells, got {len}",
+          len = cells.len()
+        ),
+      ));
+    }
+
+    Ok(Self {
+      width,
+      height,
+      cells: rows.into_iter().flatten().collect(),
+    })
+  }
+
+  /// Creates a grid of the given size with every cell set to `value`.
+  pub fn filled(width: usize, height: usize, value: T) -> Self
+  where
+    T: Clone,
+  {
+    Self {
+      width,
+      height,
+      cells: vec![value; width * height],
+    }
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn contains(&self, (row, column): Position) -> bool {
+    row < self.height && column < self.width
+  }
+
+  pub fn get(&self, position: Position) -> Option<&T> {
+    self
+      .contains(position)
+      .then(|| &self.cells[self.index(position)])
+  }
+
+  pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+    if self.contains(position) {
+      let index = self.index(position);
+      Some(&mut self.cells[index])
+    } else {
+      None
+    }
+  }
+
+  /// Moves from the position by the offset, if the result is still within the grid.
+  pub fn step(&self, (row, column): Position, (rows, columns): Offset) -> Option<Position> {
+    let position = (
+      row.checked_add_signed(rows)?,
+      column.checked_add_signed(columns)?,
+    );
+
+    self.contains(position).then_some(position)
+  }
+
+  /// Positions of all the cells, row by row.
+  pub fn positions(&self) -> impl Iterator<Item = Position> {
+    let width = self.width;
+
+    (0..self.height).flat_map(move |row| (0..width).map(move |column| (row, column)))
+  }
+
+  /// Cells along with their positions, row by row.
+  pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+    self.positions().zip(&self.cells)
+  }
+
+  /// Position of the first cell, row by row, that matches the predicate.
+  pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Position> {
+    self
+      .iter()
+      .find_map(|(position, cell)| predicate(cell).then_some(position))
+  }
+
+  /// Positions of the cells above, to the left, to the right and below, within the grid.
+  pub fn neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+    ORTHOGONAL
+      .into_iter()
+      .filter_map(move |offset| self.step(position, offset))
+  }
+
+  /// Positions of all the cells around, diagonal ones included, within the grid.
+  pub fn surrounding(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+    SURROUNDING
+      .into_iter()
+      .filter_map(move |offset| self.step(position, offset))
+  }
+
+  /// Cells of the given row, left to right. Panics if the row is outside the grid.
+  pub fn row(&self, row: usize) -> impl Iterator<Item = &T> {
+    assert!(row < self.height, "row {row} should be within the grid");
+
+    self.cells[row * self.width..(row + 1) * self.width].iter()
+  }
+
+  /// Cells of the given column, top to bottom. Panics if the column is outside the grid.
+  pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+    assert!(
+      column < self.width,
+      "column {column} should be within the grid"
+    );
+
+    self.cells[column..].iter().step_by(self.width)
+  }
+
+  /// Cells met when moving from the position by the offset until leaving the grid, along with
+  /// their positions. The starting cell is not included.
+  pub fn ray(&self, position: Position, offset: Offset) -> impl Iterator<Item = (Position, &T)> {
+    std::iter::successors(self.step(position, offset), move |&position| {
+      self.step(position, offset)
+    })
+    .map(|position| (position, &self[position]))
+  }
+
+  /// Maps every cell, keeping the layout.
+  pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+    Grid {
+      width: self.width,
+      height: self.height,
+      cells: self.cells.iter().map(f).collect(),
+    }
+  }
+
+  fn index(&self, (row, column): Position) -> usize {
+    row * self.width + column
+  }
+}
+
+impl<T> Index<Position> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, position: Position) -> &T {
+    self
+      .get(position)
+      .unwrap_or_else(|| panic!("position {position:?} should be within the grid"))
+  }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+  fn index_mut(&mut self, position: Position) -> &mut T {
+    self
+      .get_mut(position)
+      .unwrap_or_else(|| panic!("position {position:?} should be within the grid"))
+  }
+}
+
+/// Renders the grid a row per line, without a trailing line break.
+impl<T: Display> Display for Grid<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for row in 0..self.height {
+      if row > 0 {
+        writeln!(f)?;
+      }
+
+      for cell in self.row(row) {
+        write!(f, "{cell}")?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const INPUT: &str = "30373\n25512\n65332\n33549\n35390\n";
+
+  fn digits(input: &str) -> Result<Grid<u32>> {
+    Grid::parse(input, |c| {
+      c.to_digit(10)
+        .ok_or_else(|| Error::parse(format!("invalid digit `{c}`")))
+    })
+  }
+
+  #[test]
+  fn test_parse() {
+    let grid = digits(INPUT).unwrap();
+
+    assert_eq!((grid.width(), grid.height()), (5, 5));
+    assert_eq!(grid[(1, 2)], 5);
+    assert_eq!(grid.get((5, 0)), None);
+    assert_eq!(grid.to_string(), INPUT.trim_end());
+
+    let err = digits("303\n2x5\n").unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 2, column 2: invalid digit `x`"
+    );
+    assert_eq!(
+      err.snippet().map(ToString::to_string).as_deref(),
+      Some("  |\n2 | 2x5\n  |  ^")
+    );
+
+    assert_eq!(
+      digits("303\n25\n").unwrap_err().to_string(),
+      "invalid input at line 2, column 1: expected a row of 3 cells, got 2"
+    );
+    assert!(digits("").is_err());
+  }
+
+  #[test]
+  fn test_iterators() {
+    let grid = digits(INPUT).unwrap();
+
+    assert_eq!(grid.row(1).copied().collect::<Vec<_>>(), [2, 5, 5, 1, 2]);
+    assert_eq!(grid.column(4).copied().collect::<Vec<_>>(), [3, 2, 2, 9, 0]);
+    assert_eq!(
+      grid.ray((2, 2), (0, 1)).collect::<Vec<_>>(),
+      [((2, 3), &3), ((2, 4), &2)]
+    );
+    assert_eq!(grid.ray((0, 0), (-1, 0)).count(), 0);
+
+    assert_eq!(
+      grid.neighbours((0, 0)).collect::<Vec<_>>(),
+      [(0, 1), (1, 0)]
+    );
+    assert_eq!(grid.surrounding((0, 0)).count(), 3);
+    assert_eq!(grid.surrounding((2, 2)).count(), 8);
+
+    assert_eq!(grid.find(|&height| height == 9), Some((3, 4)));
+    assert_eq!(grid.positions().last(), Some((4, 4)));
+    assert_eq!(grid.map(|height| height * 2)[(3, 4)], 18);
+  }
+
+  #[test]
+  #[should_panic(expected = "column 5 should be within the grid")]
+  fn test_column_outside() {
+    // The cells are stored row by row, so column 5 would otherwise start on the second row.
+    let _ = digits(INPUT).unwrap().column(5);
+  }
+}
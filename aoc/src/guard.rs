@@ -0,0 +1,96 @@
+//! Isolation of solver phases. A panicking phase is caught and reported as an [Error::Panic] for
+//! that day and part only, so the rest of the run goes on.
+
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use crate::{Error, Result};
+
+thread_local! {
+  /// Whether a guarded phase is running on this thread.
+  static GUARDED: Cell<bool> = const { Cell::new(false) };
+
+  /// Message of the last panic in a guarded phase, along with where it happened.
+  static PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Installs a panic hook that records panics of guarded phases instead of printing them, since they
+/// are reported along with the rest of the results. Other panics are printed as usual.
+pub(crate) fn install() {
+  static INSTALL: Once = Once::new();
+
+  INSTALL.call_once(|| {
+    let default = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+      if GUARDED.get() {
+        let message = format!(
+          "{payload}{location}",
+          payload = payload(info.payload()),
+          location = info
+            .location()
+            .map(|location| format!(" at {location}"))
+            .unwrap_or_default()
+        );
+
+        PANIC.set(Some(message));
+      } else {
+        default(info);
+      }
+    }));
+  });
+}
+
+/// Runs a phase, turning a panic into an error.
+pub(crate) fn guarded<T>(phase: impl FnOnce() -> Result<T>) -> Result<T> {
+  let guarded = GUARDED.replace(true);
+
+  // Phases only get shared references to the parsed input, so nothing is left half-updated by a
+  // panic that later phases could observe.
+  let result = panic::catch_unwind(AssertUnwindSafe(phase));
+
+  GUARDED.set(guarded);
+
+  result.unwrap_or_else(|payload| {
+    let message = PANIC
+      .take()
+      .unwrap_or_else(|| self::payload(payload.as_ref()).to_string());
+
+    Err(Error::Panic(message))
+  })
+}
+
+fn payload(payload: &(dyn Any + Send)) -> &str {
+  payload
+    .downcast_ref::<&str>()
+    .copied()
+    .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+    .unwrap_or("unknown panic")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_guarded() {
+    install();
+
+    assert_eq!(guarded(|| Ok(42)), Ok(42));
+    assert_eq!(
+      guarded::<()>(|| Err(Error::parse("nope"))),
+      Err(Error::parse("nope"))
+    );
+
+    let err = guarded::<()>(|| panic!("at the disco")).unwrap_err();
+    assert!(matches!(&err, Error::Panic(message) if message.starts_with("at the disco at ")));
+
+    let index = 3;
+    let err = guarded::<()>(|| panic!("index {index} is out of bounds")).unwrap_err();
+    assert!(
+      matches!(&err, Error::Panic(message) if message.starts_with("index 3 is out of bounds at "))
+    );
+  }
+}
@@ -0,0 +1,170 @@
+//! Puzzle input resolution. Inputs are read at runtime, so solutions can be run against any input
+//! without recompiling. Embedding inputs into binaries is opt-in via the `embed` feature.
+
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+
+/// Input known at compile time: the `input.txt` next to the day module, optionally embedded into
+/// the binary. Built with the [bundled!](crate::bundled) macro.
+#[derive(Clone, Copy)]
+pub struct Bundled {
+  pub path: &'static str,
+  pub contents: Option<&'static str>,
+}
+
+impl Bundled {
+  /// Where the bundled file is read from: its embedded contents if any, otherwise the file itself.
+  pub fn source(&self) -> Source {
+    match self.contents {
+      | Some(contents) => Source::Embedded(contents),
+      | None => Source::File(PathBuf::from(self.path)),
+    }
+  }
+}
+
+/// Creates a [Bundled] input from an absolute path, embedding the file if the `embed` feature is
+/// enabled.
+#[cfg(feature = "embed")]
+#[macro_export]
+macro_rules! bundled {
+  ($path:literal) => {
+    $crate::input::Bundled {
+      path: $path,
+      contents: Some(include_str!($path)),
+    }
+  };
+}
+
+/// Creates a [Bundled] input from an absolute path, embedding the file if the `embed` feature is
+/// enabled.
+#[cfg(not(feature = "embed"))]
+#[macro_export]
+macro_rules! bundled {
+  ($path:literal) => {
+    $crate::input::Bundled {
+      path: $path,
+      contents: None,
+    }
+  };
+}
+
+/// Where a puzzle input is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+  Stdin,
+  File(PathBuf),
+  Embedded(&'static str),
+}
+
+impl Source {
+  /// Resolves the input source of a single puzzle. In order of precedence:
+  ///
+  /// 1. an explicit `file`, where `-` stands for stdin;
+  /// 2. stdin, if it's `piped` or redirected, see [piped];
+  /// 3. `<dir>/<year>/day_<day>.txt` if an inputs `dir` is given;
+  /// 4. the embedded input, if any;
+  /// 5. the bundled `input.txt` next to the day module.
+  pub fn resolve(
+    year: u16,
+    day: u8,
+    bundled: &Bundled,
+    file: Option<&Path>,
+    piped: bool,
+    dir: Option<&Path>,
+  ) -> Self {
+    if let Some(file) = file {
+      if file == Path::new("-") {
+        Source::Stdin
+      } else {
+        Source::File(file.to_path_buf())
+      }
+    } else if piped {
+      Source::Stdin
+    } else if let Some(dir) = dir {
+      Source::File(dir.join(year.to_string()).join(format!("day_{day}.txt")))
+    } else {
+      bundled.source()
+    }
+  }
+
+  pub fn read(&self) -> io::Result<String> {
+    match self {
+      | Source::Stdin => {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+      },
+      | Source::File(path) => fs::read_to_string(path),
+      | Source::Embedded(contents) => Ok(contents.to_string()),
+    }
+  }
+}
+
+/// Whether stdin is piped or redirected rather than attached to a terminal, e.g.
+/// `cat input.txt | aoc_2022 --day 9`.
+pub fn piped() -> bool {
+  !io::stdin().is_terminal()
+}
+
+impl Display for Source {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      | Source::Stdin => write!(f, "stdin"),
+      | Source::File(path) => write!(f, "{}", path.display()),
+      | Source::Embedded(_) => write!(f, "embedded input"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const BUNDLED: Bundled = Bundled {
+    path: "/aoc_2022/src/day_1/input.txt",
+    contents: None,
+  };
+
+  #[test]
+  fn test_resolve() {
+    let file = Path::new("other.txt");
+    let dir = Path::new("inputs");
+
+    assert_eq!(
+      Source::resolve(2022, 1, &BUNDLED, Some(Path::new("-")), false, Some(dir)),
+      Source::Stdin
+    );
+
+    assert_eq!(
+      Source::resolve(2022, 1, &BUNDLED, Some(file), true, Some(dir)),
+      Source::File(file.to_path_buf())
+    );
+
+    assert_eq!(
+      Source::resolve(2022, 1, &BUNDLED, None, true, Some(dir)),
+      Source::Stdin
+    );
+
+    assert_eq!(
+      Source::resolve(2022, 1, &BUNDLED, None, false, Some(dir)),
+      Source::File(PathBuf::from("inputs/2022/day_1.txt"))
+    );
+
+    assert_eq!(
+      Source::resolve(2022, 1, &BUNDLED, None, false, None),
+      Source::File(PathBuf::from(BUNDLED.path))
+    );
+
+    let embedded = Bundled {
+      contents: Some("1000"),
+      ..BUNDLED
+    };
+
+    assert_eq!(
+      Source::resolve(2022, 1, &embedded, None, false, None),
+      Source::Embedded("1000")
+    );
+  }
+}
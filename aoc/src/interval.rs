@@ -0,0 +1,560 @@
+//! Integer intervals and sets of them. Intervals are half-open, i.e. `[start, end)`, which keeps
+//! splitting and shifting free of off-by-one adjustments; [Interval::inclusive] converts from the
+//! inclusive ranges puzzles are often written with.
+
+use std::fmt::{self, Display};
+
+/// Half-open interval of integers. Intervals with `end <= start` are empty.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Interval {
+  pub start: i64,
+  pub end: i64,
+}
+
+impl Interval {
+  pub fn new(start: i64, end: i64) -> Self {
+    Self { start, end }
+  }
+
+  /// Interval from `first` to `last`, both included. The end saturates, so `i64::MAX` itself is
+  /// never included.
+  pub fn inclusive(first: i64, last: i64) -> Self {
+    Self::new(first, last.saturating_add(1))
+  }
+
+  /// Interval of `len` integers starting at `start`, saturating at the end like
+  /// [Interval::inclusive].
+  pub fn with_len(start: i64, len: i64) -> Self {
+    Self::new(start, start.saturating_add(len))
+  }
+
+  pub fn len(&self) -> i64 {
+    (self.end - self.start).max(0)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.end <= self.start
+  }
+
+  pub fn contains(&self, value: i64) -> bool {
+    self.start <= value && value < self.end
+  }
+
+  /// Whether every integer of `other` is in this interval as well.
+  pub fn covers(&self, other: &Interval) -> bool {
+    other.is_empty() || (self.start <= other.start && other.end <= self.end)
+  }
+
+  /// Whether the intervals have any integer in common.
+  pub fn overlaps(&self, other: &Interval) -> bool {
+    self.intersection(other).is_some()
+  }
+
+  /// Integers in both intervals, if there are any.
+  pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+    let interval = Interval::new(self.start.max(other.start), self.end.min(other.end));
+
+    (!interval.is_empty()).then_some(interval)
+  }
+
+  /// Splits the interval into the integers below `point` and the rest. Either side is `None` if it
+  /// would be empty.
+  pub fn split_at(&self, point: i64) -> (Option<Interval>, Option<Interval>) {
+    let point = point.clamp(self.start, self.end.max(self.start));
+    let below = Interval::new(self.start, point);
+    let above = Interval::new(point, self.end);
+
+    (
+      (!below.is_empty()).then_some(below),
+      (!above.is_empty()).then_some(above),
+    )
+  }
+
+  /// Moves the interval by `offset`. Both ends saturate, so an interval pushed past the bounds of
+  /// `i64` is cut at them.
+  pub fn shift(&self, offset: i64) -> Interval {
+    Interval::new(
+      self.start.saturating_add(offset),
+      self.end.saturating_add(offset),
+    )
+  }
+}
+
+impl Display for Interval {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "[{}, {})", self.start, self.end)
+  }
+}
+
+/// Set of integers stored as sorted, disjoint and non-adjacent intervals, so every set has exactly
+/// one representation and sets can be compared directly.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+  intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Intervals of the set, in ascending order.
+  pub fn intervals(&self) -> &[Interval] {
+    &self.intervals
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.intervals.is_empty()
+  }
+
+  /// Number of integers in the set.
+  pub fn len(&self) -> i64 {
+    self.intervals.iter().map(Interval::len).sum()
+  }
+
+  /// Smallest integer in the set.
+  pub fn min(&self) -> Option<i64> {
+    self.intervals.first().map(|interval| interval.start)
+  }
+
+  /// Largest integer in the set.
+  pub fn max(&self) -> Option<i64> {
+    self.intervals.last().map(|interval| interval.end - 1)
+  }
+
+  pub fn contains(&self, value: i64) -> bool {
+    let index = self
+      .intervals
+      .partition_point(|interval| interval.end <= value);
+
+    self
+      .intervals
+      .get(index)
+      .is_some_and(|interval| interval.contains(value))
+  }
+
+  pub fn insert(&mut self, interval: Interval) {
+    *self = self.union(&IntervalSet::from(interval));
+  }
+
+  pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+    self
+      .intervals
+      .iter()
+      .chain(&other.intervals)
+      .copied()
+      .collect()
+  }
+
+  pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+    self
+      .intervals
+      .iter()
+      .flat_map(|interval| {
+        other
+          .intervals
+          .iter()
+          .filter_map(|other| interval.intersection(other))
+      })
+      .collect()
+  }
+
+  /// Integers of this set that are not in `other`.
+  pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+    let mut rest = Vec::new();
+
+    for &interval in &self.intervals {
+      let mut interval = Some(interval);
+
+      for other in &other.intervals {
+        let Some(current) = interval else {
+          break;
+        };
+
+        if let Some(common) = current.intersection(other) {
+          let (below, _) = current.split_at(common.start);
+          let (_, above) = current.split_at(common.end);
+
+          rest.extend(below);
+          interval = above;
+        }
+      }
+
+      rest.extend(interval);
+    }
+
+    rest.into_iter().collect()
+  }
+
+  /// Splits intervals of the set at the given points, so that no interval spans across any of
+  /// them. The result isn't normalized, hence a plain list of intervals.
+  pub fn split_at(&self, points: impl IntoIterator<Item = i64>) -> Vec<Interval> {
+    let mut points = points.into_iter().collect::<Vec<_>>();
+    points.sort_unstable();
+
+    let mut pieces = Vec::new();
+
+    for &interval in &self.intervals {
+      let mut rest = Some(interval);
+
+      for &point in &points {
+        let Some(current) = rest else {
+          break;
+        };
+
+        let (below, above) = current.split_at(point);
+
+        pieces.extend(below);
+        rest = above;
+      }
+
+      pieces.extend(rest);
+    }
+
+    pieces
+  }
+
+  /// Moves every interval of the set by `offset`, see [Interval::shift]. Intervals cut at the
+  /// bounds of `i64` are normalized again, since they may end up empty or touching.
+  pub fn shift(&self, offset: i64) -> IntervalSet {
+    self
+      .intervals
+      .iter()
+      .map(|interval| interval.shift(offset))
+      .collect()
+  }
+}
+
+impl From<Interval> for IntervalSet {
+  fn from(interval: Interval) -> Self {
+    [interval].into_iter().collect()
+  }
+}
+
+/// Normalizes the intervals: empty ones are dropped, and overlapping or adjacent ones are merged.
+impl FromIterator<Interval> for IntervalSet {
+  fn from_iter<I: IntoIterator<Item = Interval>>(intervals: I) -> Self {
+    let mut sorted = intervals
+      .into_iter()
+      .filter(|interval| !interval.is_empty())
+      .collect::<Vec<_>>();
+
+    sorted.sort_unstable_by_key(|interval| interval.start);
+
+    let mut intervals: Vec<Interval> = Vec::with_capacity(sorted.len());
+
+    for interval in sorted {
+      match intervals.last_mut() {
+        | Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+        | _ => intervals.push(interval),
+      }
+    }
+
+    Self { intervals }
+  }
+}
+
+/// Piecewise-linear function that shifts the integers of each piece by the piece's offset, and
+/// leaves integers outside of every piece as they are.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Piecewise {
+  /// Disjoint pieces sorted by start, along with their offsets.
+  pieces: Vec<(Interval, i64)>,
+}
+
+impl Piecewise {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Pieces of the function, sorted by start.
+  pub fn pieces(&self) -> &[(Interval, i64)] {
+    &self.pieces
+  }
+
+  /// Adds a piece shifting the integers of `source` by `offset`. Integers already covered by
+  /// another piece keep their offset, so the first piece to cover an integer wins.
+  pub fn insert(&mut self, source: Interval, offset: i64) {
+    let covered = self.pieces.iter().map(|&(piece, _)| piece).collect();
+    let uncovered = IntervalSet::from(source).difference(&covered);
+
+    self.pieces.extend(
+      uncovered
+        .intervals()
+        .iter()
+        .map(|&interval| (interval, offset)),
+    );
+
+    self.pieces.sort_unstable_by_key(|(piece, _)| piece.start);
+  }
+
+  /// Maps a single integer.
+  pub fn apply(&self, value: i64) -> i64 {
+    let index = self.pieces.partition_point(|(piece, _)| piece.end <= value);
+
+    match self.pieces.get(index) {
+      | Some((piece, offset)) if piece.contains(value) => value + offset,
+      | _ => value,
+    }
+  }
+
+  /// Maps every integer of the set, splitting its intervals wherever they cross pieces.
+  pub fn apply_set(&self, set: &IntervalSet) -> IntervalSet {
+    let mut mapped = Vec::new();
+
+    for &interval in set.intervals() {
+      let mut rest = Some(interval);
+
+      for &(piece, offset) in &self.pieces {
+        let Some(current) = rest else {
+          break;
+        };
+
+        if let Some(common) = current.intersection(&piece) {
+          let (below, _) = current.split_at(common.start);
+          let (_, above) = current.split_at(common.end);
+
+          mapped.extend(below);
+          mapped.push(common.shift(offset));
+          rest = above;
+        }
+      }
+
+      mapped.extend(rest);
+    }
+
+    mapped.into_iter().collect()
+  }
+
+  /// Composes the functions, i.e. the result maps `x` to `next.apply(self.apply(x))`. Pieces that
+  /// end up leaving integers as they are are dropped, and neighbouring pieces with the same offset
+  /// are merged.
+  pub fn then(&self, next: &Piecewise) -> Piecewise {
+    // The composition is linear between bounds of this function's pieces and the points this
+    // function maps onto bounds of the next one's pieces.
+    let mut points = Vec::new();
+
+    for &(piece, _) in &self.pieces {
+      points.extend([piece.start, piece.end]);
+    }
+
+    for &(piece, _) in &next.pieces {
+      for bound in [piece.start, piece.end] {
+        points.push(bound);
+        points.extend(
+          self
+            .pieces
+            .iter()
+            .filter(|&&(source, offset)| source.contains(bound - offset))
+            .map(|&(_, offset)| bound - offset),
+        );
+      }
+    }
+
+    points.sort_unstable();
+    points.dedup();
+
+    let mut composed = Piecewise::new();
+
+    for bounds in points.windows(2) {
+      let segment = Interval::new(bounds[0], bounds[1]);
+      let offset = next.apply(self.apply(segment.start)) - segment.start;
+
+      match composed.pieces.last_mut() {
+        | _ if offset == 0 => {},
+        | Some((last, last_offset)) if last.end == segment.start && *last_offset == offset => {
+          last.end = segment.end;
+        },
+        | _ => composed.pieces.push((segment, offset)),
+      }
+    }
+
+    composed
+  }
+
+  /// Integers mapped into the set, i.e. the inverse image of the set.
+  pub fn preimage(&self, set: &IntervalSet) -> IntervalSet {
+    let domain = self.pieces.iter().map(|&(piece, _)| piece).collect();
+
+    // Integers outside of every piece map to themselves.
+    let mut preimage = set.difference(&domain);
+
+    for &(piece, offset) in &self.pieces {
+      let source = set.shift(-offset).intersection(&IntervalSet::from(piece));
+      preimage = preimage.union(&source);
+    }
+
+    preimage
+  }
+}
+
+/// Renders the pieces as a table, a piece per line, e.g. `[98, 100) -> [50, 52) (-48)`.
+impl Display for Piecewise {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (index, (piece, offset)) in self.pieces.iter().enumerate() {
+      if index > 0 {
+        writeln!(f)?;
+      }
+
+      write!(f, "{piece} -> {} ({offset:+})", piece.shift(*offset))?;
+    }
+
+    Ok(())
+  }
+}
+
+impl FromIterator<(Interval, i64)> for Piecewise {
+  fn from_iter<I: IntoIterator<Item = (Interval, i64)>>(pieces: I) -> Self {
+    let mut piecewise = Piecewise::new();
+
+    for (source, offset) in pieces {
+      piecewise.insert(source, offset);
+    }
+
+    piecewise
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn set(intervals: &[(i64, i64)]) -> IntervalSet {
+    intervals
+      .iter()
+      .map(|&(start, end)| Interval::new(start, end))
+      .collect()
+  }
+
+  #[test]
+  fn test_interval() {
+    let interval = Interval::inclusive(2, 8);
+
+    assert_eq!(interval, Interval::new(2, 9));
+    assert_eq!(interval.len(), 7);
+    assert!(interval.contains(8) && !interval.contains(9));
+    assert!(interval.covers(&Interval::inclusive(3, 7)));
+    assert!(!interval.covers(&Interval::inclusive(3, 9)));
+    assert!(interval.overlaps(&Interval::inclusive(8, 10)));
+    assert!(!interval.overlaps(&Interval::new(9, 12)));
+    assert_eq!(
+      interval.split_at(5),
+      (Some(Interval::new(2, 5)), Some(Interval::new(5, 9)))
+    );
+    assert_eq!(interval.split_at(0), (None, Some(interval)));
+    assert_eq!(interval.shift(-2), Interval::new(0, 7));
+    assert_eq!(interval.to_string(), "[2, 9)");
+
+    assert_eq!(Interval::inclusive(0, i64::MAX), Interval::new(0, i64::MAX));
+    assert_eq!(
+      Interval::with_len(i64::MAX - 1, 5),
+      Interval::new(i64::MAX - 1, i64::MAX)
+    );
+    assert_eq!(
+      Interval::new(i64::MAX - 4, i64::MAX).shift(2),
+      Interval::new(i64::MAX - 2, i64::MAX)
+    );
+    assert_eq!(
+      Interval::new(i64::MIN, i64::MIN + 4).shift(-2),
+      Interval::new(i64::MIN, i64::MIN + 2)
+    );
+    assert!(Interval::new(i64::MAX - 4, i64::MAX - 2)
+      .shift(5)
+      .is_empty());
+  }
+
+  #[test]
+  fn test_set() {
+    let a = set(&[(5, 8), (0, 3), (2, 4), (8, 9), (12, 12)]);
+    let b = set(&[(1, 6), (10, 20)]);
+
+    assert_eq!(a, set(&[(0, 4), (5, 9)]));
+    assert_eq!(a.len(), 8);
+    assert_eq!((a.min(), a.max()), (Some(0), Some(8)));
+    assert!(a.contains(3) && !a.contains(4) && a.contains(8));
+
+    assert_eq!(a.union(&b), set(&[(0, 9), (10, 20)]));
+    assert_eq!(a.intersection(&b), set(&[(1, 4), (5, 6)]));
+    assert_eq!(a.difference(&b), set(&[(0, 1), (6, 9)]));
+    assert_eq!(b.difference(&a), set(&[(4, 5), (10, 20)]));
+    assert_eq!(a.shift(10), set(&[(10, 14), (15, 19)]));
+    assert_eq!(
+      a.shift(i64::MAX - 6),
+      set(&[(i64::MAX - 6, i64::MAX - 2), (i64::MAX - 1, i64::MAX)])
+    );
+    assert_eq!(a.shift(i64::MAX), IntervalSet::new());
+    assert_eq!(
+      a.split_at([6, 2]),
+      [
+        Interval::new(0, 2),
+        Interval::new(2, 4),
+        Interval::new(5, 6),
+        Interval::new(6, 9)
+      ]
+    );
+
+    let mut c = IntervalSet::new();
+    c.insert(Interval::new(4, 5));
+    assert_eq!(a.union(&c), set(&[(0, 9)]));
+  }
+
+  #[test]
+  fn test_piecewise() {
+    // The seed-to-soil map of 2023 day 5.
+    let map = [
+      (Interval::with_len(98, 2), 50 - 98),
+      (Interval::with_len(50, 48), 52 - 50),
+    ]
+    .into_iter()
+    .collect::<Piecewise>();
+
+    assert_eq!(
+      [79, 14, 55, 13, 99].map(|x| map.apply(x)),
+      [81, 14, 57, 13, 51]
+    );
+    assert_eq!(
+      map.apply_set(&set(&[(45, 55), (97, 101)])),
+      set(&[(45, 57), (99, 101)])
+    );
+
+    // Overlapping pieces keep the offset of the first one.
+    let map = [(Interval::new(0, 10), 1), (Interval::new(5, 15), 2)]
+      .into_iter()
+      .collect::<Piecewise>();
+
+    assert_eq!(
+      map.pieces(),
+      [(Interval::new(0, 10), 1), (Interval::new(10, 15), 2)]
+    );
+    assert_eq!(map.apply(7), 8);
+    assert_eq!(map.apply(12), 14);
+  }
+
+  #[test]
+  fn test_compose() {
+    let first = [(Interval::new(0, 10), 5)]
+      .into_iter()
+      .collect::<Piecewise>();
+    let second = [(Interval::new(8, 12), -8), (Interval::new(15, 20), -15)]
+      .into_iter()
+      .collect::<Piecewise>();
+
+    let composed = first.then(&second);
+
+    for x in -5..30 {
+      assert_eq!(composed.apply(x), second.apply(first.apply(x)), "at {x}");
+    }
+
+    assert_eq!(
+      composed.to_string(),
+      "[0, 3) -> [5, 8) (+5)\n[3, 7) -> [0, 4) (-3)\n[7, 10) -> [12, 15) (+5)\n[10, 12) -> [2, 4) (-8)\n[15, 20) -> [0, 5) (-15)"
+    );
+
+    // Only 1 maps to 6, while 6, 11 and 18 all map to 3.
+    assert_eq!(
+      composed.preimage(&set(&[(3, 4), (6, 7)])),
+      set(&[(1, 2), (6, 7), (11, 12), (18, 19)])
+    );
+    assert_eq!(composed.preimage(&IntervalSet::new()), IntervalSet::new());
+  }
+}
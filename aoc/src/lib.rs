@@ -1,44 +1,497 @@
-use std::fmt::Display;
+use std::any::Any;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
 
-use indoc::printdoc;
+pub use answer::Answer;
+use answers::{Answers, Verdict};
+pub use error::{Error, Location, Result};
+use input::Bundled;
+use report::{timed, PartReport, Report};
 
-pub trait Printable {
-  fn print(&self);
-}
+mod answer;
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod diagnostic;
+mod error;
+pub mod geometry;
+pub mod grid;
+mod guard;
+pub mod input;
+pub mod interval;
+pub mod ocr;
+mod parallel;
+pub mod parse;
+pub mod report;
+pub mod search;
 
 /// This enum is sometimes used to specify which part of the puzzle to solve.
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Part {
   One,
   Two,
 }
 
-pub struct Solution<'s, O: Display, T: Display> {
-  pub title: &'s str,
-  pub part_one: O,
-  pub part_two: T,
+impl Part {
+  pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      | Part::One => write!(f, "1"),
+      | Part::Two => write!(f, "2"),
+    }
+  }
+}
+
+impl FromStr for Part {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      | "1" | "one" => Ok(Part::One),
+      | "2" | "two" => Ok(Part::Two),
+      | _ => Err(format!("expected `1` or `2`, got `{s}`")),
+    }
+  }
+}
+
+/// How forgiving parsing is about malformed input.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+  /// Malformed input is an error pointing at the offending line and column.
+  #[default]
+  Strict,
+  /// Malformed pieces of input are skipped where a solver supports it, see
+  /// [Solver::parse_lenient].
+  Lenient,
 }
 
-impl<'s, O: Display, T: Display> Printable for Solution<'s, O, T> {
-  fn print(&self) {
-    let Solution {
-      title,
-      part_one,
-      part_two,
-    } = self;
+/// A puzzle solver split into phases: the input is parsed once, and then both parts are solved
+/// over the parsed value. This way parsing can be timed and tested separately from solving. Every
+/// phase may fail with an [Error], which is reported for that day and part only.
+pub trait Solver {
+  /// Puzzle title, e.g. `Day 1: Calorie Counting`.
+  const TITLE: &'static str;
+
+  /// Parsed representation of the puzzle input, shared by both parts.
+  type Parsed;
+
+  /// Answer to the first part, see [Answer] for the supported types.
+  type PartOne: Into<Answer>;
+
+  /// Answer to the second part, see [Answer] for the supported types.
+  type PartTwo: Into<Answer>;
+
+  /// Parses the raw puzzle input, rejecting anything malformed.
+  fn parse(input: &str) -> Result<Self::Parsed>;
 
-    printdoc! {"
-      {title}
+  /// Parses the raw puzzle input, skipping malformed pieces instead of failing. Only used when
+  /// explicitly requested, since a skipped line usually means a wrong answer. Defaults to the
+  /// strict [Solver::parse].
+  fn parse_lenient(input: &str) -> Result<Self::Parsed> {
+    Self::parse(input)
+  }
+
+  /// Solves the first part over the parsed input.
+  fn part_one(parsed: &Self::Parsed) -> Result<Self::PartOne>;
+
+  /// Solves the second part over the parsed input.
+  fn part_two(parsed: &Self::Parsed) -> Result<Self::PartTwo>;
+
+  /// Parses the raw input like the runner does and solves the first part. Mostly useful in tests.
+  fn solve_part_one(input: &str) -> Result<Self::PartOne> {
+    Self::part_one(&parse_normalized::<Self>(input, Mode::Strict)?)
+  }
+
+  /// Parses the raw input like the runner does and solves the second part. Mostly useful in tests.
+  fn solve_part_two(input: &str) -> Result<Self::PartTwo> {
+    Self::part_two(&parse_normalized::<Self>(input, Mode::Strict)?)
+  }
+}
 
-      - Part 1: {part_one}
-      - Part 2: {part_two}
+/// Parses the input with the given solver after [normalizing](parse::normalize) its line endings,
+/// so that the runner, [Solver::solve_part_one] and [answers::assert] all see the same input.
+pub(crate) fn parse_normalized<S>(input: &str, mode: Mode) -> Result<S::Parsed>
+where
+  S: Solver + ?Sized,
+{
+  let input = parse::normalize(input);
 
-    "};
+  match mode {
+    | Mode::Strict => S::parse(&input),
+    | Mode::Lenient => S::parse_lenient(&input),
   }
 }
 
-pub fn print_solutions(solutions: &[Box<dyn Printable>]) {
-  for solution in solutions {
-    solution.print();
+/// Type-erased parsed input, see [Solver::Parsed].
+type Parsed = Box<dyn Any>;
+
+/// Lazily evaluated solution for a single day. Building it is free: the input is parsed and the
+/// parts are solved only when [Solution::run] or [Puzzle::execute] is called.
+#[derive(Clone, Copy)]
+pub struct Solution {
+  title: &'static str,
+  mode: Mode,
+  parse: fn(&str, Mode) -> Result<Parsed>,
+  part_one: fn(&Parsed) -> Result<Answer>,
+  part_two: fn(&Parsed) -> Result<Answer>,
+}
+
+impl Solution {
+  /// Creates a deferred solution for the given [Solver].
+  pub fn new<S>() -> Self
+  where
+    S: Solver,
+    S::Parsed: 'static,
+  {
+    Self {
+      title: S::TITLE,
+      mode: Mode::Strict,
+      parse: |input, mode| Ok(Box::new(parse_normalized::<S>(input, mode)?)),
+      part_one: |parsed| S::part_one(downcast::<S>(parsed)).map(Into::into),
+      part_two: |parsed| S::part_two(downcast::<S>(parsed)).map(Into::into),
+    }
+  }
+
+  /// Sets how forgiving parsing is, strict by default.
+  pub fn with_mode(mut self, mode: Mode) -> Self {
+    self.mode = mode;
+    self
+  }
+
+  pub fn title(&self) -> &'static str {
+    self.title
+  }
+
+  /// Parses the input and solves only the given part. Panics are reported as [Error::Panic].
+  pub fn run(&self, input: &str, part: Part) -> Result<Answer> {
+    let parsed = self.parse(input)?;
+
+    self.solve(&parsed, part)
+  }
+
+  fn parse(&self, input: &str) -> Result<Parsed> {
+    guard::guarded(|| (self.parse)(input, self.mode))
+  }
+
+  fn solve(&self, parsed: &Parsed, part: Part) -> Result<Answer> {
+    guard::guarded(|| {
+      match part {
+        | Part::One => (self.part_one)(parsed),
+        | Part::Two => (self.part_two)(parsed),
+      }
+    })
+  }
+}
+
+fn downcast<S>(parsed: &Parsed) -> &S::Parsed
+where
+  S: Solver,
+  S::Parsed: 'static,
+{
+  parsed
+    .downcast_ref::<S::Parsed>()
+    .expect("parsed input should come from the same solver")
+}
+
+/// A [Solution] registered under its day number, along with its bundled input.
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+  pub day: u8,
+  pub solution: Solution,
+  pub input: Bundled,
+}
+
+impl Puzzle {
+  pub fn new(day: u8, solution: Solution, input: Bundled) -> Self {
+    Self {
+      day,
+      solution,
+      input,
+    }
+  }
+
+  /// Parses the input once and solves the given parts, timing each phase. If parsing fails, no
+  /// parts are solved. A panicking phase fails on its own, see [Error::Panic].
+  ///
+  /// With a time limit, phases run on a worker thread, and a phase that takes longer than that
+  /// fails with [Error::Timeout]. The worker can't be stopped, so it is abandoned and the remaining
+  /// parts are solved by a new one, which parses the input again.
+  pub fn execute(&self, year: u16, input: &str, parts: &[Part], limit: Option<Duration>) -> Report {
+    let mut report = Report {
+      year,
+      day: self.day,
+      title: self.solution.title,
+      parse: Duration::ZERO,
+      error: None,
+      parts: Vec::new(),
+    };
+
+    let Some(limit) = limit else {
+      phases(&self.solution, input, parts, |event| {
+        record(&mut report, parts, event)
+      });
+
+      return report;
+    };
+
+    let wait = |receiver: &Receiver<Event>| {
+      receiver.recv_timeout(limit).map_err(|err| {
+        match err {
+          | RecvTimeoutError::Timeout => Error::Timeout(limit),
+          | RecvTimeoutError::Disconnected => Error::Panic("the worker thread exited".to_string()),
+        }
+      })
+    };
+
+    let mut first = true;
+
+    while first || report.parts.len() < parts.len() {
+      let pending = &parts[report.parts.len()..];
+      let receiver = spawn(self.solution, input, pending);
+
+      let parsed = wait(&receiver);
+
+      if first {
+        first = false;
+
+        match parsed {
+          | Ok(event) => record(&mut report, parts, event),
+          | Err(err) => record(&mut report, parts, Event::Parsed(Err(err), limit)),
+        }
+
+        if report.error.is_some() {
+          break;
+        }
+      } else if let Err(err) | Ok(Event::Parsed(Err(err), _)) = parsed {
+        // Parsing the same input again isn't supposed to fail, but if it does, so do the parts.
+        for _ in pending {
+          record(
+            &mut report,
+            parts,
+            Event::Solved(Err(err.clone()), Duration::ZERO),
+          );
+        }
+
+        break;
+      }
+
+      for _ in pending {
+        match wait(&receiver) {
+          | Ok(event) => record(&mut report, parts, event),
+          | Err(err) => {
+            record(&mut report, parts, Event::Solved(Err(err), limit));
+            break;
+          },
+        }
+      }
+    }
+
+    report
+  }
+}
+
+/// Progress of a puzzle run, reported phase by phase.
+enum Event {
+  Parsed(Result<()>, Duration),
+  Solved(Result<Answer>, Duration),
+}
+
+/// Parses the input and solves the given parts in order, reporting each phase once it's done.
+fn phases(solution: &Solution, input: &str, parts: &[Part], mut report: impl FnMut(Event)) {
+  let (parsed, time) = timed(|| solution.parse(input));
+
+  let parsed = match parsed {
+    | Ok(parsed) => {
+      report(Event::Parsed(Ok(()), time));
+      parsed
+    },
+    | Err(err) => return report(Event::Parsed(Err(err), time)),
+  };
+
+  for &part in parts {
+    let (answer, time) = timed(|| solution.solve(&parsed, part));
+    report(Event::Solved(answer, time));
+  }
+}
+
+/// Runs [phases] on a worker thread, sending events back over the returned channel.
+fn spawn(solution: Solution, input: &str, parts: &[Part]) -> Receiver<Event> {
+  let (sender, receiver) = mpsc::channel();
+  let input = input.to_string();
+  let parts = parts.to_vec();
+
+  thread::spawn(move || {
+    // Sending fails once the phase being waited for timed out, the rest is of no interest then.
+    phases(&solution, &input, &parts, |event| drop(sender.send(event)));
+  });
+
+  receiver
+}
+
+/// Adds a phase to the report. Parts are solved in the given order, so the next solved one is the
+/// first part that isn't reported yet.
+fn record(report: &mut Report, parts: &[Part], event: Event) {
+  match event {
+    | Event::Parsed(result, time) => {
+      report.parse = time;
+      report.error = result.err();
+    },
+    | Event::Solved(answer, time) => {
+      report.parts.push(PartReport {
+        part: parts[report.parts.len()],
+        answer,
+        time,
+        verdict: Verdict::Unknown,
+      });
+    },
+  }
+}
+
+/// Puzzles of a single event, ordered by day, along with the manifest of their known answers.
+pub struct Year {
+  pub year: u16,
+  pub puzzles: Vec<Puzzle>,
+  pub answers: Option<Bundled>,
+}
+
+impl Year {
+  pub fn new(year: u16, mut puzzles: Vec<Puzzle>) -> Self {
+    puzzles.sort_by_key(|puzzle| puzzle.day);
+
+    Self {
+      year,
+      puzzles,
+      answers: None,
+    }
+  }
+
+  /// Sets the bundled answers manifest, see [answers].
+  pub fn with_answers(mut self, answers: Bundled) -> Self {
+    self.answers = Some(answers);
+    self
+  }
+
+  /// Reads and parses the answers manifest. A year without one has no known answers.
+  pub fn answers(&self) -> Result<Answers, String> {
+    let Some(bundled) = &self.answers else {
+      return Ok(Answers::default());
+    };
+
+    let source = bundled.source();
+    let manifest = source
+      .read()
+      .map_err(|err| format!("failed to read {source}: {err}"))?;
+
+    Answers::parse(&manifest).map_err(|err| format!("invalid manifest {source}: {err}"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Solver sleeping for as many milliseconds as its input says in the first part, also used by
+  /// the tests of [crate::parallel].
+  pub(crate) struct Slow;
+
+  impl Solver for Slow {
+    type Parsed = u64;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    const TITLE: &'static str = "Day 1: Slow";
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+      Ok(input.trim().parse()?)
+    }
+
+    fn part_one(millis: &Self::Parsed) -> Result<Self::PartOne> {
+      thread::sleep(Duration::from_millis(*millis));
+      Ok(*millis)
+    }
+
+    fn part_two(millis: &Self::Parsed) -> Result<Self::PartTwo> {
+      Ok(millis * 2)
+    }
+  }
+
+  /// Solver summing numbers on separate lines, which fails on any leftover `\r`.
+  struct Lines;
+
+  impl Solver for Lines {
+    type Parsed = Vec<u64>;
+    type PartOne = u64;
+    type PartTwo = usize;
+
+    const TITLE: &'static str = "Day 1: Lines";
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+      input.split('\n').map(|line| Ok(line.parse()?)).collect()
+    }
+
+    fn part_one(numbers: &Self::Parsed) -> Result<Self::PartOne> {
+      Ok(numbers.iter().sum())
+    }
+
+    fn part_two(numbers: &Self::Parsed) -> Result<Self::PartTwo> {
+      Ok(numbers.len())
+    }
+  }
+
+  #[test]
+  fn test_normalized() {
+    let input = "1\r\n2\r\n\r\n";
+
+    assert!(Lines::parse(input).is_err());
+    assert_eq!(Lines::solve_part_one(input), Ok(3));
+    assert_eq!(Lines::solve_part_two(input), Ok(2));
+    assert_eq!(
+      Solution::new::<Lines>().run(input, Part::One),
+      Ok(Answer::Integer(3))
+    );
+
+    answers::assert::<Lines>("[day_1]\npart_1 = \"3\"\npart_2 = \"2\"", 1, input);
+  }
+
+  #[test]
+  fn test_execute_limited() {
+    let puzzle = Puzzle::new(
+      1,
+      Solution::new::<Slow>(),
+      Bundled {
+        path: "input.txt",
+        contents: None,
+      },
+    );
+    let limit = Some(Duration::from_millis(50));
+
+    let report = puzzle.execute(2022, "500", &Part::ALL, limit);
+    let answers = report
+      .parts
+      .iter()
+      .map(|part| part.answer.clone())
+      .collect::<Vec<_>>();
+
+    assert_eq!(report.error, None);
+    assert_eq!(
+      answers,
+      [
+        Err(Error::Timeout(Duration::from_millis(50))),
+        Ok(Answer::Integer(1000))
+      ]
+    );
+
+    let report = puzzle.execute(2022, "5", &Part::ALL, limit);
+    assert!(!report.failed());
+
+    let report = puzzle.execute(2022, "x", &Part::ALL, limit);
+    assert!(matches!(report.error, Some(Error::Parse { .. })));
+    assert!(report.parts.is_empty());
   }
 }
@@ -0,0 +1,210 @@
+//! Letter recognition for puzzles whose answer is drawn on a screen, e.g. a CRT. Both AoC fonts
+//! are supported: the common one with letters 6 pixels high, and the large one with letters 10
+//! pixels high. Lit pixels are `#` or `█`, anything else is dark.
+
+use std::ops::Range;
+
+use crate::{Answer, Error, Result};
+
+/// Letters of the font 6 pixels high, usually 4 pixels wide and a blank column apart.
+const SMALL: &[(char, &[&str])] = &[
+  ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+  ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+  ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+  ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+  ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+  ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+  ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+  ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+  ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+  ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+  ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+  ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+  ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+  ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+  ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+  ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+  ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+  ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Letters of the font 10 pixels high, usually 6 pixels wide and two blank columns apart.
+#[rustfmt::skip]
+const LARGE: &[(char, &[&str])] = &[
+  ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+  ('B', &["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+  ('C', &[".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+  ('E', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+  ('F', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+  ('G', &[".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+  ('H', &["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+  ('J', &["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+  ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+  ('L', &["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+  ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+  ('P', &["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+  ('R', &["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+  ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+  ('Z', &["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// Pixels of an image, row by row.
+type Pixels = Vec<Vec<bool>>;
+
+/// Reads the letters drawn in the image. The font is picked by the height of the image, and
+/// letters are told apart by the blank columns between them, so their exact spacing doesn't
+/// matter. Fails if the height matches neither font or any of the letters isn't known.
+pub fn recognize(image: &str) -> Result<String> {
+  let image = pixels(image.trim_matches('\n').lines());
+
+  let font = match image.len() {
+    | 6 => SMALL,
+    | 10 => LARGE,
+    | height => {
+      return Err(Error::no_solution(format!(
+        "expected an image 6 or 10 pixels high, got {height}"
+      )))
+    },
+  };
+
+  let letters = spans(&image);
+
+  if letters.is_empty() {
+    return Err(Error::no_solution("no letters in the image"));
+  }
+
+  letters
+    .into_iter()
+    .map(|span| {
+      let letter = cut(&image, span.clone());
+
+      font
+        .iter()
+        .find(|(_, glyph)| {
+          let glyph = pixels(glyph.iter().copied());
+          spans(&glyph).first().map(|span| cut(&glyph, span.clone())) == Some(letter.clone())
+        })
+        .map(|&(letter, _)| letter)
+        .ok_or_else(|| {
+          Error::no_solution(format!(
+            "unrecognized letter at column {column}",
+            column = span.start + 1
+          ))
+        })
+    })
+    .collect()
+}
+
+/// Reads the letters drawn in the image into an answer that keeps the image too, so it can still be
+/// printed. If the letters can't be read, the answer is the image alone.
+pub fn read(image: &str) -> Answer {
+  match recognize(image) {
+    | Ok(text) => {
+      Answer::Screen {
+        text,
+        image: image
+          .trim_matches('\n')
+          .lines()
+          .map(str::to_string)
+          .collect(),
+      }
+    },
+    | Err(_) => Answer::from(image),
+  }
+}
+
+fn pixels<'a>(rows: impl Iterator<Item = &'a str>) -> Pixels {
+  rows
+    .map(|row| row.chars().map(|c| matches!(c, '#' | '█')).collect())
+    .collect()
+}
+
+/// Ranges of columns with lit pixels, separated by blank columns.
+fn spans(image: &Pixels) -> Vec<Range<usize>> {
+  let width = image.iter().map(Vec::len).max().unwrap_or(0);
+  let lit = |column: usize| image.iter().any(|row| row.get(column) == Some(&true));
+
+  let mut spans = Vec::new();
+  let mut start = None;
+
+  for column in 0..=width {
+    match (start, column < width && lit(column)) {
+      | (None, true) => start = Some(column),
+      | (Some(from), false) => {
+        spans.push(from..column);
+        start = None;
+      },
+      | _ => {},
+    }
+  }
+
+  spans
+}
+
+/// Cuts the given columns out of the image, padding short rows with dark pixels.
+fn cut(image: &Pixels, span: Range<usize>) -> Pixels {
+  image
+    .iter()
+    .map(|row| {
+      span
+        .clone()
+        .map(|column| row.get(column).copied().unwrap_or(false))
+        .collect()
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_small() {
+    let image = indoc::indoc! {"
+      ###..#..#.#....#..#...##..##..####..##..
+      #..#.#..#.#....#..#....#.#..#....#.#..#.
+      #..#.####.#....####....#.#......#..#..#.
+      ###..#..#.#....#..#....#.#.##..#...####.
+      #....#..#.#....#..#.#..#.#..#.#....#..#.
+      #....#..#.####.#..#..##...###.####.#..#.
+    "};
+
+    assert_eq!(recognize(image), Ok("PHLHJGZA".to_string()));
+  }
+
+  #[test]
+  fn test_large() {
+    let image = indoc::indoc! {"
+      #....#..######
+      #....#..#.....
+      .#..#...#.....
+      .#..#...#.....
+      ..##....#####.
+      ..##....#.....
+      .#..#...#.....
+      .#..#...#.....
+      #....#..#.....
+      #....#..######
+    "};
+
+    assert_eq!(recognize(image), Ok("XE".to_string()));
+  }
+
+  #[test]
+  fn test_unrecognized() {
+    assert_eq!(
+      recognize("##..##\n##..##\n##..##\n##..##\n##..##\n##..##"),
+      Err(Error::no_solution("unrecognized letter at column 1"))
+    );
+    assert_eq!(
+      recognize("#\n#"),
+      Err(Error::no_solution(
+        "expected an image 6 or 10 pixels high, got 2"
+      ))
+    );
+    assert_eq!(
+      recognize("....\n....\n....\n....\n....\n...."),
+      Err(Error::no_solution("no letters in the image"))
+    );
+  }
+}
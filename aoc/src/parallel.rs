@@ -0,0 +1,173 @@
+//! Solving puzzles on a pool of threads. Reports are handed back in the order the puzzles were
+//! given, so the output doesn't depend on which puzzle happens to finish first.
+
+use std::collections::BTreeMap;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread::Scope;
+use std::time::Duration;
+
+use crate::report::Report;
+use crate::{Part, Puzzle};
+
+/// A puzzle to solve along with its input.
+pub(crate) struct Task<'a> {
+  pub year: u16,
+  pub puzzle: Puzzle,
+  pub input: &'a str,
+}
+
+/// Reports of tasks being solved on the pool, see [solve].
+pub(crate) struct Reports {
+  receiver: Receiver<(usize, Report)>,
+  received: BTreeMap<usize, Vec<Report>>,
+  units: usize,
+}
+
+impl Reports {
+  /// Waits until the task at the given index is solved.
+  pub fn get(&mut self, index: usize) -> Report {
+    while self.received.get(&index).map_or(0, Vec::len) < self.units {
+      let (index, report) = self
+        .receiver
+        .recv()
+        .expect("workers should outlive the reports");
+
+      self.received.entry(index).or_default().push(report);
+    }
+
+    let mut reports = self.received.remove(&index).unwrap_or_default();
+
+    // Parts solved separately are merged back in order, with the parse time of the first part.
+    reports.sort_by_key(|report| report.parts.first().map(|part| part.part));
+
+    let mut merged = reports.remove(0);
+
+    for report in reports {
+      merged.parts.extend(report.parts);
+    }
+
+    merged
+  }
+}
+
+/// Starts solving the tasks, keyed by their index, on the given number of threads. If parts are
+/// split, each part of a task is solved on its own, parsing the input again.
+pub(crate) fn solve<'scope, 'env>(
+  scope: &'scope Scope<'scope, 'env>,
+  tasks: &'env [(usize, Task<'env>)],
+  parts: &'env [Part],
+  threads: usize,
+  split_parts: bool,
+  limit: Option<Duration>,
+) -> Reports {
+  let units = if split_parts {
+    tasks
+      .iter()
+      .flat_map(|(index, task)| parts.chunks(1).map(move |parts| (*index, task, parts)))
+      .collect::<Vec<_>>()
+  } else {
+    tasks
+      .iter()
+      .map(|(index, task)| (*index, task, parts))
+      .collect::<Vec<_>>()
+  };
+
+  let count = if split_parts { parts.len() } else { 1 };
+  let queue = Arc::new(Mutex::new(units.into_iter()));
+  let (sender, receiver) = mpsc::channel();
+
+  for _ in 0..threads.max(1) {
+    let queue = Arc::clone(&queue);
+    let sender = sender.clone();
+
+    scope.spawn(move || {
+      loop {
+        // The lock is released right away, so that other workers can pick up tasks meanwhile.
+        let next = queue.lock().map(|mut queue| queue.next());
+
+        let Ok(Some((index, task, parts))) = next else {
+          break;
+        };
+
+        let report = task.puzzle.execute(task.year, task.input, parts, limit);
+
+        if sender.send((index, report)).is_err() {
+          break;
+        }
+      }
+    });
+  }
+
+  // Only workers hold senders now, so the receiver fails instead of blocking if they are all gone.
+  drop(sender);
+
+  Reports {
+    receiver,
+    received: BTreeMap::new(),
+    units: count,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::thread;
+
+  use super::*;
+  use crate::input::Bundled;
+  use crate::tests::Slow;
+  use crate::{Answer, Solution};
+
+  #[test]
+  fn test_solve_parallel() {
+    let tasks = ["30", "0", "10"]
+      .into_iter()
+      .enumerate()
+      .map(|(index, input)| {
+        let puzzle = Puzzle::new(
+          index as u8 + 1,
+          Solution::new::<Slow>(),
+          Bundled {
+            path: "input.txt",
+            contents: None,
+          },
+        );
+
+        (
+          index,
+          Task {
+            year: 2022,
+            puzzle,
+            input,
+          },
+        )
+      })
+      .collect::<Vec<_>>();
+
+    // Later puzzles finish first, yet reports come back in order with their parts merged.
+    let answers = thread::scope(|scope| {
+      let mut reports = solve(scope, &tasks, &Part::ALL, 3, true, None);
+
+      (0..tasks.len())
+        .map(|index| {
+          reports
+            .get(index)
+            .parts
+            .into_iter()
+            .map(|part| (part.part, part.answer))
+            .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>()
+    });
+
+    assert_eq!(
+      answers,
+      [30, 0, 10].map(|millis: u64| {
+        vec![
+          (Part::One, Ok(Answer::from(millis))),
+          (Part::Two, Ok(Answer::from(millis * 2))),
+        ]
+      })
+    );
+  }
+}
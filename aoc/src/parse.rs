@@ -0,0 +1,267 @@
+//! Helpers for the parsing chores most puzzles share: splitting the input into paragraphs, reading
+//! `key: value` lines and picking numbers out of text. Errors point at the offending piece of the
+//! text the helper was given, see [Error::within] to point at the whole input instead.
+
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::diagnostic::Snippet;
+use crate::{Error, Result};
+
+/// Converts CRLF line endings to LF and strips trailing newlines, so that inputs saved on any
+/// platform parse the same. Borrows the input if there is nothing to convert.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+  let input = input.trim_end_matches(['\r', '\n']);
+
+  if input.contains("\r\n") {
+    Cow::Owned(input.replace("\r\n", "\n"))
+  } else {
+    Cow::Borrowed(input)
+  }
+}
+
+/// Splits the input into paragraphs separated by blank lines, i.e. lines with whitespace only.
+/// Paragraphs are slices of the input without their trailing newline, so they can be pointed at
+/// with [Snippet::of] and [Error::within].
+pub fn paragraphs(input: &str) -> impl Iterator<Item = &str> {
+  let mut rest = input;
+
+  std::iter::from_fn(move || {
+    let start = loop {
+      let (line, tail) = rest.split_once('\n').unwrap_or((rest, ""));
+
+      if !line.trim().is_empty() {
+        break rest;
+      }
+
+      if tail.is_empty() {
+        return None;
+      }
+
+      rest = tail;
+    };
+
+    let mut end = 0;
+
+    while !rest.is_empty() {
+      let (line, tail) = rest.split_once('\n').unwrap_or((rest, ""));
+
+      if line.trim().is_empty() {
+        break;
+      }
+
+      end = start.len() - rest.len() + line.len();
+      rest = tail;
+    }
+
+    Some(start[..end].trim_end_matches('\r'))
+  })
+}
+
+/// Splits a `key: value` line at the first colon, trimming whitespace around both, e.g. `Time:  7
+/// 15` into `Time` and `7  15`.
+pub fn key_value(line: &str) -> Option<(&str, &str)> {
+  line
+    .split_once(':')
+    .map(|(key, value)| (key.trim(), value.trim()))
+}
+
+/// Value of a `key: value` line, checking that the line has the given key.
+pub fn field<'a>(line: &'a str, key: &str) -> Result<&'a str> {
+  match key_value(line) {
+    | Some((found, value)) if found == key => Ok(value),
+    | Some((found, _)) => {
+      let message = format!("unexpected key `{found}`");
+      Err(Error::at(line, found, message, [format!("`{key}`")]))
+    },
+    | None => {
+      let message = format!("invalid `{key}` line");
+      Err(Error::at(
+        line,
+        line.trim(),
+        message,
+        [format!("`{key}: <value>`")],
+      ))
+    },
+  }
+}
+
+/// Parses values separated by `separator`, ignoring whitespace around them, e.g. `79, 98` with `,`.
+/// A whitespace separator matches any run of whitespace, e.g. in columns like `41 48  3`.
+pub fn list<T>(text: &str, separator: char) -> Result<Vec<T>>
+where
+  T: FromStr,
+  T::Err: Display,
+{
+  if separator.is_whitespace() {
+    text
+      .split_whitespace()
+      .map(|item| value(text, item))
+      .collect()
+  } else if text.trim().is_empty() {
+    Ok(Vec::new())
+  } else {
+    text
+      .split(separator)
+      .map(|item| value(text, item.trim()))
+      .collect()
+  }
+}
+
+/// Picks all the unsigned integers out of arbitrary text, e.g. `3`, `1` and `2` out of `move 3
+/// from 1 to 2`. Everything between the digits is skipped, minus signs included.
+pub fn unsigned<T>(text: &str) -> Result<Vec<T>>
+where
+  T: FromStr,
+  T::Err: Display,
+{
+  integers(text, false)
+}
+
+/// Picks all the integers out of arbitrary text, like [unsigned], except that a minus sign right
+/// before the digits makes the number negative, e.g. `-3` and `4` out of `x=-3, y=4`.
+pub fn signed<T>(text: &str) -> Result<Vec<T>>
+where
+  T: FromStr,
+  T::Err: Display,
+{
+  integers(text, true)
+}
+
+fn integers<T>(text: &str, signed: bool) -> Result<Vec<T>>
+where
+  T: FromStr,
+  T::Err: Display,
+{
+  let bytes = text.as_bytes();
+  let mut numbers = Vec::new();
+  let mut index = 0;
+
+  while index < bytes.len() {
+    if !bytes[index].is_ascii_digit() {
+      index += 1;
+      continue;
+    }
+
+    let mut start = index;
+
+    while index < bytes.len() && bytes[index].is_ascii_digit() {
+      index += 1;
+    }
+
+    if signed && start > 0 && bytes[start - 1] == b'-' {
+      start -= 1;
+    }
+
+    numbers.push(value(text, &text[start..index])?);
+  }
+
+  Ok(numbers)
+}
+
+/// Parses `part`, a slice of `text`, pointing at it if it's invalid.
+fn value<T>(text: &str, part: &str) -> Result<T>
+where
+  T: FromStr,
+  T::Err: Display,
+{
+  part.parse::<T>().map_err(|err| {
+    Error::parse(format!("invalid value `{part}`: {err}")).with_snippet(Snippet::of(text, part))
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use indoc::indoc;
+
+  use super::*;
+
+  #[test]
+  fn test_normalize() {
+    assert_eq!(normalize("1\r\n2\r\n\r\n"), "1\n2");
+    assert_eq!(normalize("1\n\n2\n"), "1\n\n2");
+    assert!(matches!(normalize("1\n2\n"), Cow::Borrowed("1\n2")));
+  }
+
+  #[test]
+  fn test_paragraphs() {
+    let input = indoc! {"
+
+      Monkey 0:
+        Items: 1, 2
+
+      \t
+      Monkey 1:
+        Items: 3
+    "};
+
+    assert_eq!(
+      paragraphs(input).collect::<Vec<_>>(),
+      ["Monkey 0:\n  Items: 1, 2", "Monkey 1:\n  Items: 3"]
+    );
+
+    let input = "1\r\n2\r\n\r\n3";
+    assert_eq!(paragraphs(input).collect::<Vec<_>>(), ["1\r\n2", "3"]);
+
+    assert_eq!(paragraphs("").count(), 0);
+    assert_eq!(paragraphs("\n \n").count(), 0);
+  }
+
+  #[test]
+  fn test_key_value() {
+    assert_eq!(
+      key_value("Time:      7  15   30"),
+      Some(("Time", "7  15   30"))
+    );
+    assert_eq!(
+      key_value("  Operation: new = old * 19").unwrap().0,
+      "Operation"
+    );
+    assert_eq!(key_value("no colon"), None);
+  }
+
+  #[test]
+  fn test_field() {
+    assert_eq!(field("Distance:  9 40", "Distance"), Ok("9 40"));
+
+    let err = field("Time: 7", "Distance").unwrap_err();
+    assert_eq!(
+      err.snippet().map(ToString::to_string).as_deref(),
+      Some("  |\n1 | Time: 7\n  | ^^^^ expected `Distance`")
+    );
+
+    let err = field("  Distance 9", "Distance").unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 1, column 3: invalid `Distance` line"
+    );
+  }
+
+  #[test]
+  fn test_list() {
+    assert_eq!(list::<u32>("79, 98", ','), Ok(vec![79, 98]));
+    assert_eq!(list::<u32>(" 41 48  3 ", ' '), Ok(vec![41, 48, 3]));
+    assert_eq!(list::<u32>(" ", ','), Ok(vec![]));
+
+    let err = list::<u32>("79, x8", ',').unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 1, column 5: invalid value `x8`: invalid digit found in string"
+    );
+  }
+
+  #[test]
+  fn test_integers() {
+    assert_eq!(unsigned::<u32>("move 3 from 1 to 12"), Ok(vec![3, 1, 12]));
+    assert_eq!(unsigned::<u32>("x=-3, y=4"), Ok(vec![3, 4]));
+    assert_eq!(signed::<i32>("x=-3, y=4..-10"), Ok(vec![-3, 4, -10]));
+    assert_eq!(signed::<i32>("no numbers"), Ok(vec![]));
+
+    let err = unsigned::<u8>("Test: divisible by 256").unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 1, column 20: invalid value `256`: number too large to fit in target type"
+    );
+  }
+}
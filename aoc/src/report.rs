@@ -0,0 +1,383 @@
+//! Results of running solutions: answers along with how long each phase took. Reports are either
+//! printed for humans via [Display], or rendered in bulk with [json], [csv] or [markdown].
+
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+
+use crate::answers::{Answers, Verdict};
+use crate::{Answer, Error, Part};
+
+/// Answer to a single part, or why it could not be solved, along with the time it took and whether
+/// the answer is correct.
+#[derive(Clone, Debug)]
+pub struct PartReport {
+  pub part: Part,
+  pub answer: Result<Answer, Error>,
+  pub time: Duration,
+  pub verdict: Verdict,
+}
+
+impl PartReport {
+  /// Short annotation of the verdict, e.g. `[wrong, expected 42]`. Expected multi-line answers are
+  /// left out.
+  fn mark(&self) -> String {
+    match &self.verdict {
+      | Verdict::Wrong { expected } if !expected.is_multiline() => {
+        format!("[wrong, expected {expected}]")
+      },
+      | verdict => format!("[{verdict}]"),
+    }
+  }
+}
+
+/// Answers and timings of a single puzzle run. If parsing failed, `error` is set and no parts are
+/// reported.
+#[derive(Clone, Debug)]
+pub struct Report {
+  pub year: u16,
+  pub day: u8,
+  pub title: &'static str,
+  pub parse: Duration,
+  pub error: Option<Error>,
+  pub parts: Vec<PartReport>,
+}
+
+impl Report {
+  /// Time spent parsing the input and solving all the parts.
+  pub fn total(&self) -> Duration {
+    self.parse + self.parts.iter().map(|part| part.time).sum::<Duration>()
+  }
+
+  /// Checks the answers against the manifest. Failed parts stay unknown.
+  pub fn verify(&mut self, answers: &Answers) {
+    for part in &mut self.parts {
+      if let Ok(answer) = &part.answer {
+        part.verdict = answers.verdict(self.day, part.part, answer);
+      }
+    }
+  }
+
+  /// Whether parsing or any of the parts failed.
+  pub fn failed(&self) -> bool {
+    self.error.is_some() || self.parts.iter().any(|part| part.answer.is_err())
+  }
+
+  fn part(&self, part: Part) -> Option<&PartReport> {
+    self.parts.iter().find(|report| report.part == part)
+  }
+}
+
+impl Display for Report {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "{title}\n", title = self.title)?;
+    match &self.error {
+      | Some(err) => {
+        writeln!(f, "- Parse failed: {err} ({parse:.2?})", parse = self.parse)?;
+        write_snippet(f, err)?;
+      },
+      | None => writeln!(f, "- Parse: {parse:.2?}", parse = self.parse)?,
+    }
+
+    for report in &self.parts {
+      let PartReport {
+        part, answer, time, ..
+      } = report;
+
+      let answer = match answer {
+        | Ok(answer) => answer,
+        | Err(err) => {
+          writeln!(f, "- Part {part} failed: {err} ({time:.2?})")?;
+          write_snippet(f, err)?;
+          continue;
+        },
+      };
+
+      let mark = report.mark();
+
+      // Multi-line answers (e.g. CRT screens) go below the label, indented to stay in the list.
+      // Letters read off a screen are printed as usual, followed by the screen.
+      if answer.is_multiline() {
+        writeln!(f, "- Part {part} ({time:.2?}) {mark}:")?;
+      } else {
+        writeln!(f, "- Part {part}: {answer} ({time:.2?}) {mark}")?;
+      }
+
+      for row in answer.image().unwrap_or_default() {
+        writeln!(f, "  {row}")?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// Writes the snippet of the offending input below a failure, indented to stay in the list.
+fn write_snippet(f: &mut fmt::Formatter<'_>, err: &Error) -> fmt::Result {
+  if let Some(snippet) = err.snippet() {
+    for line in snippet.to_string().lines() {
+      writeln!(f, "  {line}")?;
+    }
+  }
+
+  Ok(())
+}
+
+/// Renders reports as a JSON array. Timings are in nanoseconds, integer answers are numbers,
+/// expected answers are included for wrong ones, and failures have an `error` message instead of an
+/// answer.
+pub fn json(reports: &[Report]) -> String {
+  let reports = reports
+    .iter()
+    .map(|report| {
+      let parts = report
+        .parts
+        .iter()
+        .map(|part| {
+          let mut value = json!({
+            "part": part.part.to_string(),
+            "answer": part.answer.as_ref().ok().map(value),
+            "error": part.answer.as_ref().err().map(Error::to_string),
+            "time_ns": part.time.as_nanos() as u64,
+            "verdict": part.verdict.to_string(),
+          });
+
+          if let Verdict::Wrong { expected } = &part.verdict {
+            value["expected"] = self::value(expected);
+          }
+
+          value
+        })
+        .collect::<Vec<_>>();
+
+      json!({
+        "year": report.year,
+        "day": report.day,
+        "title": report.title,
+        "parse_ns": report.parse.as_nanos() as u64,
+        "error": report.error.as_ref().map(Error::to_string),
+        "parts": parts,
+      })
+    })
+    .collect::<Vec<_>>();
+
+  serde_json::to_string_pretty(&reports).expect("reports should serialize to JSON")
+}
+
+/// Converts an answer to JSON. Integers that don't fit a JSON number are kept as strings, just like
+/// text, and grids are strings with line breaks.
+fn value(answer: &Answer) -> serde_json::Value {
+  match answer {
+    | Answer::Integer(value) => {
+      i64::try_from(*value)
+        .map(serde_json::Value::from)
+        .or_else(|_| u64::try_from(*value).map(serde_json::Value::from))
+        .unwrap_or_else(|_| value.to_string().into())
+    },
+    | answer => answer.to_string().into(),
+  }
+}
+
+/// Renders reports as RFC 4180 CSV with a header, one row per puzzle. Timings are in nanoseconds,
+/// columns of parts that were not run are left empty, and failures fill the `error` columns.
+pub fn csv(reports: &[Report]) -> String {
+  fn field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+      format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+      value.to_string()
+    }
+  }
+
+  let mut csv = String::from(
+    "year,day,title,parse_ns,error,\
+     part_1,part_1_ns,part_1_verdict,part_1_error,\
+     part_2,part_2_ns,part_2_verdict,part_2_error\r\n",
+  );
+
+  for report in reports {
+    let mut row = vec![
+      report.year.to_string(),
+      report.day.to_string(),
+      field(report.title),
+      report.parse.as_nanos().to_string(),
+      report
+        .error
+        .as_ref()
+        .map_or_else(String::new, |err| field(&err.to_string())),
+    ];
+
+    for part in Part::ALL {
+      match report.part(part) {
+        | Some(part) => {
+          let (answer, error) = match &part.answer {
+            | Ok(answer) => (field(&answer.to_string()), String::new()),
+            | Err(err) => (String::new(), field(&err.to_string())),
+          };
+
+          row.push(answer);
+          row.push(part.time.as_nanos().to_string());
+          row.push(part.verdict.to_string());
+          row.push(error);
+        },
+        | None => row.extend([String::new(), String::new(), String::new(), String::new()]),
+      }
+    }
+
+    csv.push_str(&row.join(","));
+    csv.push_str("\r\n");
+  }
+
+  csv
+}
+
+/// Renders reports as a Markdown table, one row per puzzle. Line breaks of multi-line answers are
+/// kept as `<br>`, failures are shown in place of the timing or answer.
+pub fn markdown(reports: &[Report]) -> String {
+  fn cell(value: &str) -> String {
+    value
+      .replace('|', "\\|")
+      .lines()
+      .collect::<Vec<_>>()
+      .join("<br>")
+  }
+
+  let mut table = String::from(
+    "| Year | Day | Title | Parse | Part 1 | Time | Verdict | Part 2 | Time | Verdict |\n\
+     | ---: | --: | :---- | ----: | :----- | ---: | :------ | :----- | ---: | :------ |\n",
+  );
+
+  for report in reports {
+    let mut row = vec![
+      report.year.to_string(),
+      report.day.to_string(),
+      cell(report.title),
+      match &report.error {
+        | Some(err) => cell(&format!("failed: {err}")),
+        | None => format!("{:.2?}", report.parse),
+      },
+    ];
+
+    for part in Part::ALL {
+      match report.part(part) {
+        | Some(part) => {
+          match &part.answer {
+            | Ok(answer) => row.push(cell(&answer.to_string())),
+            | Err(err) => row.push(cell(&format!("failed: {err}"))),
+          }
+
+          row.push(format!("{:.2?}", part.time));
+          row.push(part.verdict.to_string());
+        },
+        | None => row.extend([String::new(), String::new(), String::new()]),
+      }
+    }
+
+    table.push_str(&format!("| {} |\n", row.join(" | ")));
+  }
+
+  table
+}
+
+/// Runs `f` and measures how long it took.
+pub(crate) fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+  let start = Instant::now();
+  let value = f();
+
+  (value, start.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn report() -> Report {
+    Report {
+      year: 2022,
+      day: 10,
+      title: "Day 10: Cathode-Ray Tube",
+      parse: Duration::from_micros(5),
+      error: None,
+      parts: vec![
+        PartReport {
+          part: Part::One,
+          answer: Ok(Answer::Integer(13140)),
+          time: Duration::from_micros(1),
+          verdict: Verdict::Wrong {
+            expected: Answer::Integer(15360),
+          },
+        },
+        PartReport {
+          part: Part::Two,
+          answer: Ok(Answer::parse("##..\n#..#")),
+          time: Duration::from_micros(2),
+          verdict: Verdict::Correct,
+        },
+      ],
+    }
+  }
+
+  #[test]
+  fn test_display() {
+    let report = report();
+
+    assert_eq!(report.total(), Duration::from_micros(8));
+    assert_eq!(
+      report.to_string(),
+      "Day 10: Cathode-Ray Tube\n\n- Parse: 5.00µs\n- Part 1: 13140 (1.00µs) [wrong, expected 15360]\n- Part 2 (2.00µs) [correct]:\n  ##..\n  #..#\n"
+    );
+
+    let failed = Report {
+      error: Some(Error::parse("expected a number")),
+      parts: Vec::new(),
+      ..report
+    };
+
+    assert!(failed.failed());
+    assert_eq!(
+      failed.to_string(),
+      "Day 10: Cathode-Ray Tube\n\n- Parse failed: invalid input: expected a number (5.00µs)\n"
+    );
+  }
+
+  #[test]
+  fn test_json() {
+    let json = json(&[report()]);
+    let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+
+    assert_eq!(value[0]["year"], 2022);
+    assert_eq!(value[0]["parse_ns"], 5000);
+    assert_eq!(value[0]["parts"][0]["answer"], 13140);
+    assert_eq!(value[0]["parts"][0]["expected"], 15360);
+    assert_eq!(value[0]["parts"][1]["answer"], "##..\n#..#");
+    assert_eq!(value[0]["parts"][1]["verdict"], "correct");
+  }
+
+  #[test]
+  fn test_csv() {
+    let mut failed = report();
+    failed.parts.truncate(1);
+    failed.parts[0].answer = Err(Error::parse("bad line"));
+    failed.parts[0].verdict = Verdict::Unknown;
+
+    assert_eq!(
+      csv(&[report(), failed]),
+      "year,day,title,parse_ns,error,\
+       part_1,part_1_ns,part_1_verdict,part_1_error,\
+       part_2,part_2_ns,part_2_verdict,part_2_error\r\n\
+       2022,10,Day 10: Cathode-Ray Tube,5000,,13140,1000,wrong,,\"##..\n#..#\",2000,correct,\r\n\
+       2022,10,Day 10: Cathode-Ray Tube,5000,,,1000,unknown,invalid input: bad line,,,,\r\n"
+    );
+  }
+
+  #[test]
+  fn test_markdown() {
+    let table = markdown(&[report()]);
+
+    assert_eq!(
+      table.lines().last(),
+      Some("| 2022 | 10 | Day 10: Cathode-Ray Tube | 5.00µs | 13140 | 1.00µs | wrong | ##..<br>#..# | 2.00µs | correct |")
+    );
+  }
+}
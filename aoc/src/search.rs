@@ -0,0 +1,320 @@
+//! Graph searches over any state type. The graph is never built up front: callers supply a
+//! function listing the neighbours of a state, along with the cost of moving there for weighted
+//! searches. Every search accepts several starts and returns the path it found, not just its cost.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Path from one of the starts to a goal, both included.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<S, C = usize> {
+  pub states: Vec<S>,
+  pub cost: C,
+}
+
+/// Result of a search: the path found, if any, and how many states were visited to find it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome<S, C = usize> {
+  pub path: Option<Path<S, C>>,
+  /// Number of states taken off the frontier, which is handy for profiling heuristics.
+  pub visited: usize,
+}
+
+impl<S, C> Outcome<S, C> {
+  /// Cost of the path found, if any.
+  pub fn cost(&self) -> Option<C>
+  where
+    C: Copy,
+  {
+    self.path.as_ref().map(|path| path.cost)
+  }
+}
+
+/// States seen so far, each along with the state it was reached from.
+struct Seen<S> {
+  states: Vec<S>,
+  parents: Vec<Option<usize>>,
+  indices: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash> Seen<S> {
+  fn new() -> Self {
+    Self {
+      states: Vec::new(),
+      parents: Vec::new(),
+      indices: HashMap::new(),
+    }
+  }
+
+  /// Index of the state, and whether it wasn't seen before.
+  fn insert(&mut self, state: S, parent: Option<usize>) -> (usize, bool) {
+    if let Some(&index) = self.indices.get(&state) {
+      return (index, false);
+    }
+
+    let index = self.states.len();
+
+    self.indices.insert(state.clone(), index);
+    self.states.push(state);
+    self.parents.push(parent);
+
+    (index, true)
+  }
+
+  /// States leading to the one at the given index, starting from where the search began.
+  fn path(&self, mut index: usize) -> Vec<S> {
+    let mut path = vec![self.states[index].clone()];
+
+    while let Some(parent) = self.parents[index] {
+      path.push(self.states[parent].clone());
+      index = parent;
+    }
+
+    path.reverse();
+    path
+  }
+}
+
+/// Breadth-first search, where every move costs one step. Finds the shortest path from any of the
+/// starts to a state matching `goal`.
+pub fn bfs<S, I>(
+  starts: impl IntoIterator<Item = S>,
+  mut neighbours: impl FnMut(&S) -> I,
+  mut goal: impl FnMut(&S) -> bool,
+) -> Outcome<S>
+where
+  S: Clone + Eq + Hash,
+  I: IntoIterator<Item = S>,
+{
+  let mut seen = Seen::new();
+  let mut queue = VecDeque::new();
+
+  for start in starts {
+    if let (index, true) = seen.insert(start, None) {
+      queue.push_back((index, 0));
+    }
+  }
+
+  let mut visited = 0;
+
+  while let Some((index, steps)) = queue.pop_front() {
+    visited += 1;
+
+    let state = seen.states[index].clone();
+
+    if goal(&state) {
+      return Outcome {
+        path: Some(Path {
+          states: seen.path(index),
+          cost: steps,
+        }),
+        visited,
+      };
+    }
+
+    for next in neighbours(&state) {
+      if let (next, true) = seen.insert(next, Some(index)) {
+        queue.push_back((next, steps + 1));
+      }
+    }
+  }
+
+  Outcome {
+    path: None,
+    visited,
+  }
+}
+
+/// Dijkstra's search for the cheapest path from any of the starts to a state matching `goal`.
+/// `neighbours` lists the states reachable from a state along with the cost of moving there, which
+/// must not be negative.
+pub fn dijkstra<S, C, I>(
+  starts: impl IntoIterator<Item = S>,
+  neighbours: impl FnMut(&S) -> I,
+  goal: impl FnMut(&S) -> bool,
+) -> Outcome<S, C>
+where
+  S: Clone + Eq + Hash,
+  C: Copy + Ord + Default + Add<Output = C>,
+  I: IntoIterator<Item = (S, C)>,
+{
+  astar(starts, neighbours, |_| C::default(), goal)
+}
+
+/// A* search, i.e. [dijkstra] guided by a `heuristic` estimating the cost left to reach a goal. The
+/// path is the cheapest one as long as the heuristic never overestimates. It doesn't have to be
+/// consistent too: states are reopened whenever a cheaper path to them turns up, which a consistent
+/// heuristic never causes.
+pub fn astar<S, C, I>(
+  starts: impl IntoIterator<Item = S>,
+  mut neighbours: impl FnMut(&S) -> I,
+  mut heuristic: impl FnMut(&S) -> C,
+  mut goal: impl FnMut(&S) -> bool,
+) -> Outcome<S, C>
+where
+  S: Clone + Eq + Hash,
+  C: Copy + Ord + Default + Add<Output = C>,
+  I: IntoIterator<Item = (S, C)>,
+{
+  let mut seen = Seen::new();
+  let mut costs = Vec::new();
+  let mut frontier = BinaryHeap::new();
+
+  for start in starts {
+    if let (index, true) = seen.insert(start, None) {
+      costs.push(C::default());
+      frontier.push(Reverse((heuristic(&seen.states[index]), index)));
+    }
+  }
+
+  let mut visited = 0;
+  let mut done = vec![false; costs.len()];
+
+  while let Some(Reverse((_, index))) = frontier.pop() {
+    // A state may be queued several times as cheaper paths to it are found, only the pop of the
+    // cheapest one counts.
+    if std::mem::replace(&mut done[index], true) {
+      continue;
+    }
+
+    visited += 1;
+
+    let state = seen.states[index].clone();
+    let cost = costs[index];
+
+    if goal(&state) {
+      return Outcome {
+        path: Some(Path {
+          states: seen.path(index),
+          cost,
+        }),
+        visited,
+      };
+    }
+
+    for (next, step) in neighbours(&state) {
+      let total = cost + step;
+      let (next, new) = seen.insert(next, Some(index));
+
+      if new {
+        costs.push(total);
+        done.push(false);
+      } else if total >= costs[next] {
+        continue;
+      } else {
+        costs[next] = total;
+        done[next] = false;
+        seen.parents[next] = Some(index);
+      }
+
+      frontier.push(Reverse((total + heuristic(&seen.states[next]), next)));
+    }
+  }
+
+  Outcome {
+    path: None,
+    visited,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::grid::{Grid, Position};
+
+  const MAZE: &str = "S.#.....\n..#.##.#\n..#..#..\n....#..E\n";
+
+  fn maze() -> (Grid<char>, Position, Position) {
+    let grid = Grid::parse(MAZE, Ok).unwrap();
+    let start = grid.find(|&c| c == 'S').unwrap();
+    let end = grid.find(|&c| c == 'E').unwrap();
+
+    (grid, start, end)
+  }
+
+  #[test]
+  fn test_bfs() {
+    let (grid, start, end) = maze();
+    let neighbours = |&position: &Position| {
+      grid
+        .neighbours(position)
+        .filter(|&next| grid[next] != '#')
+        .collect::<Vec<_>>()
+    };
+
+    let outcome = bfs([start], neighbours, |&position| position == end);
+    let path = outcome.path.unwrap();
+
+    assert_eq!(path.cost, 16);
+    assert_eq!(path.states.len(), 17);
+    assert_eq!(path.states.first(), Some(&start));
+    assert_eq!(path.states.last(), Some(&end));
+    assert!(path
+      .states
+      .windows(2)
+      .all(|pair| grid.neighbours(pair[0]).any(|next| next == pair[1])));
+    assert!(outcome.visited > 0);
+
+    // Starting right above the end as well is a shortcut.
+    let outcome = bfs([start, (2, 7)], neighbours, |&position| position == end);
+    assert_eq!(outcome.cost(), Some(1));
+
+    let outcome = bfs([start], neighbours, |&position| position == (1, 4));
+    assert_eq!(outcome.path, None);
+  }
+
+  #[test]
+  fn test_weighted() {
+    let (grid, start, end) = maze();
+
+    // Moving down is free, so paths heading down first are cheaper.
+    let neighbours = |&(row, column): &Position| {
+      grid
+        .neighbours((row, column))
+        .filter(|&next| grid[next] != '#')
+        .map(move |next| (next, if next.0 > row { 0 } else { 1 }))
+        .collect::<Vec<_>>()
+    };
+    let heuristic = |&(_, column): &Position| end.1.abs_diff(column);
+
+    let cheapest = dijkstra([start], neighbours, |&position| position == end);
+    let guided = astar([start], neighbours, heuristic, |&position| position == end);
+
+    assert_eq!(cheapest.cost(), Some(10));
+    assert_eq!(guided.cost(), cheapest.cost());
+    assert!(guided.visited <= cheapest.visited);
+
+    let path = guided.path.unwrap();
+    let cost = path
+      .states
+      .windows(2)
+      .map(|pair| usize::from(pair[1].0 <= pair[0].0))
+      .sum::<usize>();
+
+    assert_eq!(cost, 10);
+  }
+
+  #[test]
+  fn test_inconsistent() {
+    // `A` is on the cheapest path, but its heuristic makes `B` look more promising, so `C` is
+    // reached through `B` first. The estimates never exceed the actual costs though.
+    let neighbours = |state: &char| {
+      match state {
+        | 'S' => vec![('A', 1), ('B', 1)],
+        | 'A' => vec![('C', 1)],
+        | 'B' => vec![('C', 3)],
+        | 'C' => vec![('G', 5)],
+        | _ => vec![],
+      }
+    };
+    let heuristic = |state: &char| if *state == 'A' { 6 } else { 0 };
+
+    let outcome = astar(['S'], neighbours, heuristic, |state| *state == 'G');
+    let path = outcome.path.unwrap();
+
+    assert_eq!(path.cost, 7);
+    assert_eq!(path.states, ['S', 'A', 'C', 'G']);
+  }
+}
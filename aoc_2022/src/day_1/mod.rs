@@ -2,9 +2,7 @@
 //!
 //! [link]: https://adventofcode.com/2022/day/1
 
-use aoc::Solution;
-
-const INPUT: &str = include_str!("input.txt");
+use aoc::{parse, Error, Mode, Result, Solution, Solver};
 
 struct Calories<const N: usize> {
   values: [u64; N],
@@ -43,44 +41,98 @@ impl<const N: usize> FromIterator<u64> for Calories<N> {
   }
 }
 
-fn solve<const N: usize>(input: &str) -> u64 {
-  // Looks dirty, but works.
-  input
-    .split("\n\n")
-    .map(|elf| {
-      elf
-        .lines()
-        .map(|line| line.parse::<u64>().unwrap_or_default())
-        .sum()
-    })
-    // Just playing around with const generics and iterators...
-    .collect::<Calories<N>>()
-    .sum()
+fn top<const N: usize>(elves: &[u64]) -> u64 {
+  // Just playing around with const generics and iterators...
+  elves.iter().copied().collect::<Calories<N>>().sum()
 }
 
-pub fn solution<'s>() -> Solution<'s, u64, u64> {
-  Solution {
-    title: "Day 1: Calorie Counting",
-    part_one: solve::<1>(INPUT),
-    part_two: solve::<3>(INPUT),
+/// Sums calories of each elf, elves being separated by blank lines. In lenient mode lines that
+/// aren't numbers are skipped.
+fn parse_elves(input: &str, mode: Mode) -> Result<Vec<u64>> {
+  let mut elves = Vec::new();
+
+  for elf in parse::paragraphs(input) {
+    let mut calories = 0;
+
+    for line in elf.lines() {
+      match line.parse::<u64>() {
+        | Ok(value) => calories += value,
+        | Err(_) if mode == Mode::Lenient => {},
+        | Err(err) => {
+          let message = format!("invalid calories `{line}`: {err}");
+          return Err(Error::at(input, line, message, ["a number"]));
+        },
+      }
+    }
+
+    elves.push(calories);
   }
+
+  Ok(elves)
+}
+
+struct Day;
+
+impl Solver for Day {
+  /// Total calories carried by each elf.
+  type Parsed = Vec<u64>;
+  type PartOne = u64;
+  type PartTwo = u64;
+
+  const TITLE: &'static str = "Day 1: Calorie Counting";
+
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    parse_elves(input, Mode::Strict)
+  }
+
+  fn parse_lenient(input: &str) -> Result<Self::Parsed> {
+    parse_elves(input, Mode::Lenient)
+  }
+
+  fn part_one(elves: &Self::Parsed) -> Result<Self::PartOne> {
+    Ok(top::<1>(elves))
+  }
+
+  fn part_two(elves: &Self::Parsed) -> Result<Self::PartTwo> {
+    Ok(top::<3>(elves))
+  }
+}
+
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
   const EXAMPLE: &str = include_str!("example.txt");
 
   #[test]
   fn test_examples() {
-    assert_eq!(solve::<1>(EXAMPLE), 24000);
-    assert_eq!(solve::<3>(EXAMPLE), 45000);
+    assert_eq!(Day::solve_part_one(EXAMPLE), Ok(24000));
+    assert_eq!(Day::solve_part_two(EXAMPLE), Ok(45000));
+  }
+
+  #[test]
+  fn test_malformed() {
+    let input = "1000\n2000\n\n3000\nfoo\n";
+
+    assert_eq!(
+      Day::parse(input).map_err(|err| err.to_string()),
+      Err(
+        "invalid input at line 5, column 1: invalid calories `foo`: invalid digit found in string"
+          .to_string()
+      )
+    );
+
+    assert_eq!(Day::parse_lenient(input), Ok(vec![3000, 3000]));
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve::<1>(INPUT), 70374);
-    assert_eq!(solve::<3>(INPUT), 204610);
+    aoc::answers::assert::<Day>(ANSWERS, 1, INPUT);
   }
 }
@@ -4,16 +4,7 @@
 
 use std::str::FromStr;
 
-use aoc::Solution;
-
-const INPUT: &str = include_str!("input.txt");
-
-#[derive(Debug)]
-enum ParseError {
-  UnknownInstruction,
-  InvalidAddXValue,
-  InvalidAddXInstruction,
-}
+use aoc::{Answer, Error, Result, Solution, Solver};
 
 #[derive(Clone, Debug)]
 enum Instruction {
@@ -22,7 +13,7 @@ enum Instruction {
 }
 
 impl FromStr for Instruction {
-  type Err = ParseError;
+  type Err = Error;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
     if s.starts_with("noop") {
@@ -30,15 +21,24 @@ impl FromStr for Instruction {
     } else if s.starts_with("addx") {
       match s.split_once(' ') {
         | Some((_, value)) => {
-          value
-            .parse::<isize>()
-            .map(Instruction::Addx)
-            .map_err(|_| ParseError::InvalidAddXValue)
+          value.parse::<isize>().map(Instruction::Addx).map_err(|_| {
+            Error::at(
+              s,
+              value,
+              format!("invalid addx value `{value}`"),
+              &["a number"],
+            )
+          })
+        },
+        | None => {
+          let message = format!("invalid addx instruction `{s}`");
+          Err(Error::at(s, s, message, &["`addx <value>`"]))
         },
-        | None => Err(ParseError::InvalidAddXInstruction),
       }
     } else {
-      Err(ParseError::UnknownInstruction)
+      let instruction = s.split_whitespace().next().unwrap_or(s);
+      let message = format!("unknown instruction `{instruction}`");
+      Err(Error::at(s, instruction, message, &["`noop`", "`addx`"]))
     }
   }
 }
@@ -106,42 +106,57 @@ impl Device {
   }
 }
 
-fn solve_part_one(input: &str) -> isize {
+fn run(program: &[Instruction]) -> Device {
   let mut device = Device::new();
 
-  input
-    .lines()
-    .map(str::parse::<Instruction>)
-    .map(Result::unwrap)
-    .for_each(|instruction| device.execute(&instruction));
+  program
+    .iter()
+    .for_each(|instruction| device.execute(instruction));
 
-  device.signal
+  device
 }
 
-fn solve_part_two(input: &str) -> String {
-  let mut device = Device::new();
+struct Day;
+
+impl Solver for Day {
+  type Parsed = Vec<Instruction>;
+  type PartOne = isize;
+  /// Letters drawn on the screen, or the screen itself if they can't be read.
+  type PartTwo = Answer;
+
+  const TITLE: &'static str = "Day 10: Cathode-Ray Tube";
+
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    input
+      .lines()
+      .enumerate()
+      .map(|(index, line)| {
+        line
+          .parse::<Instruction>()
+          .map_err(|err| err.on_line(index + 1))
+      })
+      .collect()
+  }
 
-  input
-    .lines()
-    .map(str::parse::<Instruction>)
-    .map(Result::unwrap)
-    .for_each(|instruction| device.execute(&instruction));
+  fn part_one(program: &Self::Parsed) -> Result<Self::PartOne> {
+    Ok(run(program).signal)
+  }
 
-  device.to_crt()
+  fn part_two(program: &Self::Parsed) -> Result<Self::PartTwo> {
+    Ok(aoc::ocr::read(&run(program).to_crt()))
+  }
 }
 
-pub fn solution<'s>() -> Solution<'s, isize, String> {
-  Solution {
-    title: "Day 10: Cathode-Ray Tube",
-    part_one: solve_part_one(INPUT),
-    part_two: solve_part_two(INPUT),
-  }
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
   const EXAMPLE: &str = include_str!("example.txt");
 
   #[test]
@@ -155,22 +170,29 @@ mod tests {
       #######.......#######.......#######.....
     "};
 
-    assert_eq!(solve_part_one(EXAMPLE), 13140);
-    assert_eq!(solve_part_two(EXAMPLE), part_two.trim());
+    assert_eq!(Day::solve_part_one(EXAMPLE), Ok(13140));
+    assert_eq!(
+      Day::solve_part_two(EXAMPLE),
+      Ok(Answer::from(part_two.trim()))
+    );
   }
 
   #[test]
-  fn test_input() {
-    let part_two = indoc::indoc! {"
-      ###..#..#.#....#..#...##..##..####..##..
-      #..#.#..#.#....#..#....#.#..#....#.#..#.
-      #..#.####.#....####....#.#......#..#..#.
-      ###..#..#.#....#..#....#.#.##..#...####.
-      #....#..#.#....#..#.#..#.#..#.#....#..#.
-      #....#..#.####.#..#..##...###.####.#..#.
-    "};
+  fn test_ocr() {
+    let crt = run(&Day::parse(INPUT).unwrap()).to_crt();
+
+    assert_eq!(aoc::ocr::recognize(&crt), Ok("PHLHJGZA".to_string()));
+    assert_eq!(
+      Day::solve_part_two(INPUT)
+        .unwrap()
+        .image()
+        .map(|rows| rows.join("\n")),
+      Some(crt.trim_end().to_string())
+    );
+  }
 
-    assert_eq!(solve_part_one(INPUT), 15360);
-    assert_eq!(solve_part_two(INPUT), part_two.trim());
+  #[test]
+  fn test_input() {
+    aoc::answers::assert::<Day>(ANSWERS, 10, INPUT);
   }
 }
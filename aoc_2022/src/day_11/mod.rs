@@ -4,18 +4,7 @@
 
 use std::str::FromStr;
 
-use aoc::Solution;
-
-const INPUT: &str = include_str!("input.txt");
-
-#[derive(Debug)]
-enum ParseError {
-  Line,
-  Operation,
-  OperationValue,
-  TestBy,
-  IfValue,
-}
+use aoc::{parse, Error, Result, Solution, Solver};
 
 #[derive(Clone, Copy, Debug)]
 enum Operation {
@@ -35,27 +24,54 @@ impl Operation {
 }
 
 impl FromStr for Operation {
-  type Err = ParseError;
+  type Err = Error;
 
+  /// Parses a whole `Operation: new = old * 19` line.
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    let parts = s
-      .split_ascii_whitespace()
-      .map(str::trim)
-      .collect::<Vec<_>>();
+    let Some((_, binop)) = s.split_once('=') else {
+      let message = format!("invalid operation `{}`", s.trim());
+      return Err(Error::at(
+        s,
+        s.trim(),
+        message,
+        &["`Operation: new = <expression>`"],
+      ));
+    };
+
+    let parts = binop.split_ascii_whitespace().collect::<Vec<_>>();
 
     match parts.as_slice() {
       | ["old", "*", "old"] => Ok(Operation::MultiplySelf),
       | ["old", "*", n] => {
-        n.parse::<usize>()
-          .map(Operation::Multiply)
-          .map_err(|_| ParseError::OperationValue)
+        n.parse::<usize>().map(Operation::Multiply).map_err(|_| {
+          Error::at(
+            s,
+            n,
+            format!("invalid operation value `{n}`"),
+            &["a number", "`old`"],
+          )
+        })
       },
       | ["old", "+", n] => {
-        n.parse::<usize>()
-          .map(Operation::Add)
-          .map_err(|_| ParseError::OperationValue)
+        n.parse::<usize>().map(Operation::Add).map_err(|_| {
+          Error::at(
+            s,
+            n,
+            format!("invalid operation value `{n}`"),
+            &["a number"],
+          )
+        })
+      },
+      | _ => {
+        let binop = binop.trim();
+        let message = format!("invalid operation `{binop}`");
+        Err(Error::at(
+          s,
+          binop,
+          message,
+          &["`old * <value>`", "`old + <value>`"],
+        ))
       },
-      | _ => Err(ParseError::Operation),
     }
   }
 }
@@ -77,84 +93,76 @@ struct Monkey {
 }
 
 impl Monkey {
-  fn from_lines(lines: &[&str], current: &mut usize) -> Result<Monkey, ParseError> {
-    // Parse `Starting items`.
-
-    let items = lines[*current + 1]
-      .trim()
-      .split_once(':')
-      .ok_or(ParseError::Line)?;
-
-    let items = match items {
-      | (_, values) => {
-        values
-          .split(',')
-          .map(str::trim)
-          .filter_map(|value| value.parse::<usize>().ok())
-          .collect::<Vec<_>>()
-      },
+  /// Parses a paragraph of the input describing a single monkey.
+  fn from_paragraph(input: &str, paragraph: &str) -> Result<Monkey> {
+    let mut lines = paragraph.lines();
+    let header = lines.next().unwrap_or_default();
+
+    let mut line = |key: &str| {
+      let line = lines.next().ok_or_else(|| {
+        let expected = format!("`{key}: ...`");
+        Error::at(input, header.trim(), "incomplete monkey", [expected])
+      })?;
+
+      parse::field(line, key)
+        .map(|value| (line, value))
+        .map_err(|err| err.within(input, line))
     };
 
-    // Parse `Operation`.
-
-    let operation = &lines[*current + 2]
-      .trim()
-      .split_once('=')
-      .ok_or(ParseError::Line)?;
-
-    let operation = match operation {
-      | (_, binop) => binop.trim().parse::<Operation>()?,
-    };
-
-    // Parse `Test`.
-
-    let test_mod = lines[*current + 3]
-      .trim()
-      .split_once("by")
-      .ok_or(ParseError::Line)?;
-
-    let test_mod = match test_mod {
-      | (_, divisible_by) => {
-        divisible_by
-          .trim()
-          .parse::<usize>()
-          .map_err(|_| ParseError::TestBy)?
-      },
-    };
+    // Parse `Starting items`.
 
-    // Parse `If true`.
+    let (_, items) = line("Starting items")?;
+    let items = parse::list(items, ',').map_err(|err| err.within(input, items))?;
 
-    let if_true = lines[*current + 4]
-      .trim()
-      .split_once("monkey")
-      .ok_or(ParseError::Line)?;
+    // Parse `Operation`.
 
-    let if_true = match if_true {
-      | (_, value) => {
-        value
-          .trim()
-          .parse::<usize>()
-          .map_err(|_| ParseError::IfValue)?
-      },
+    let (operation, _) = line("Operation")?;
+    let operation = operation
+      .parse::<Operation>()
+      .map_err(|err| err.within(input, operation))?;
+
+    // Parse `Test` and the `If true` and `If false` targets, which are numbers after some words.
+
+    let number = |value: &str, prefix: &str, expected: &str| {
+      match value.strip_prefix(prefix).map(str::trim) {
+        | Some(number) => {
+          number.parse::<usize>().map_err(|_| {
+            Error::at(
+              input,
+              number,
+              format!("invalid number `{number}`"),
+              ["a number"],
+            )
+          })
+        },
+        | None => {
+          Err(Error::at(
+            input,
+            value,
+            format!("invalid `{value}`"),
+            [expected],
+          ))
+        },
+      }
     };
 
-    // Parse `If false`.
+    let (_, test) = line("Test")?;
+    let test_mod = number(test, "divisible by", "`divisible by <divisor>`")?;
 
-    let if_false = lines[*current + 5]
-      .trim()
-      .split_once("monkey")
-      .ok_or(ParseError::Line)?;
+    if test_mod == 0 {
+      return Err(Error::at(
+        input,
+        test,
+        "can't divide by zero",
+        ["a positive number"],
+      ));
+    }
 
-    let if_false = match if_false {
-      | (_, value) => {
-        value
-          .trim()
-          .parse::<usize>()
-          .map_err(|_| ParseError::IfValue)?
-      },
-    };
+    let (_, if_true) = line("If true")?;
+    let if_true = number(if_true, "throw to monkey", "`throw to monkey <id>`")?;
 
-    *current += 7;
+    let (_, if_false) = line("If false")?;
+    let if_false = number(if_false, "throw to monkey", "`throw to monkey <id>`")?;
 
     Ok(Monkey {
       items,
@@ -167,22 +175,26 @@ impl Monkey {
   }
 }
 
-fn parse_monkeys(lines: &[&str]) -> Result<Vec<Monkey>, ParseError> {
-  let mut monkeys = Vec::new();
-  let mut current = 0;
+fn parse_monkeys(input: &str) -> Result<Vec<Monkey>> {
+  let monkeys = parse::paragraphs(input)
+    .map(|paragraph| Monkey::from_paragraph(input, paragraph))
+    .collect::<Result<Vec<_>>>()?;
 
-  while current < lines.len() {
-    monkeys.push(Monkey::from_lines(lines, &mut current)?);
+  for (index, monkey) in monkeys.iter().enumerate() {
+    if monkey.if_true >= monkeys.len() || monkey.if_false >= monkeys.len() {
+      return Err(Error::parse(format!(
+        "monkey {index} throws to a monkey that doesn't exist"
+      )));
+    }
   }
 
   Ok(monkeys)
 }
 
-fn solve_part_one(input: &str) -> usize {
+fn simulate_relieved(monkeys: &[Monkey]) -> usize {
   const ROUNDS: usize = 20;
 
-  let lines = input.lines().collect::<Vec<_>>();
-  let mut monkeys = parse_monkeys(&lines).unwrap();
+  let mut monkeys = monkeys.to_vec();
 
   for _ in 0..ROUNDS {
     for monkey_index in 0..monkeys.len() {
@@ -192,7 +204,7 @@ fn solve_part_one(input: &str) -> usize {
         let item = monkeys[monkey_index].items.remove(0);
         let item = monkeys[monkey_index].operation.perform(item) / 3;
 
-        let next_monkey = if item % monkeys[monkey_index].test_mod == 0 {
+        let next_monkey = if item.is_multiple_of(monkeys[monkey_index].test_mod) {
           monkeys[monkey_index].if_true
         } else {
           monkeys[monkey_index].if_false
@@ -215,11 +227,10 @@ fn solve_part_one(input: &str) -> usize {
   inspected_counts.into_iter().take(2).product()
 }
 
-fn solve_part_two(input: &str) -> usize {
+fn simulate_worried(monkeys: &[Monkey]) -> usize {
   const ROUNDS: usize = 10000;
 
-  let lines = input.lines().collect::<Vec<_>>();
-  let mut monkeys = parse_monkeys(&lines).unwrap();
+  let mut monkeys = monkeys.to_vec();
 
   // Working off a modulus of the product of all the test_mods allows
   // for the operations to be congruent to the mod of all the monkey test_mods
@@ -233,7 +244,7 @@ fn solve_part_two(input: &str) -> usize {
         let mut item = monkeys[index].items.remove(0);
         item = monkeys[index].operation.perform(item) % mod_product;
 
-        let next_monkey = if item % monkeys[index].test_mod == 0 {
+        let next_monkey = if item.is_multiple_of(monkeys[index].test_mod) {
           monkeys[index].if_true
         } else {
           monkeys[index].if_false
@@ -253,29 +264,71 @@ fn solve_part_two(input: &str) -> usize {
   inspected_counts.into_iter().take(2).product()
 }
 
-pub fn solution<'s>() -> Solution<'s, usize, usize> {
-  Solution {
-    title: "Day 11: Monkey in the Middle",
-    part_one: solve_part_one(INPUT),
-    part_two: solve_part_two(INPUT),
+struct Day;
+
+impl Solver for Day {
+  type Parsed = Vec<Monkey>;
+  type PartOne = usize;
+  type PartTwo = usize;
+
+  const TITLE: &'static str = "Day 11: Monkey in the Middle";
+
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    parse_monkeys(input)
+  }
+
+  fn part_one(monkeys: &Self::Parsed) -> Result<Self::PartOne> {
+    Ok(simulate_relieved(monkeys))
+  }
+
+  fn part_two(monkeys: &Self::Parsed) -> Result<Self::PartTwo> {
+    Ok(simulate_worried(monkeys))
   }
 }
 
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
   const EXAMPLE: &str = include_str!("example.txt");
 
   #[test]
   fn test_examples() {
-    assert_eq!(solve_part_one(EXAMPLE), 10605);
-    assert_eq!(solve_part_two(EXAMPLE), 2713310158);
+    assert_eq!(Day::solve_part_one(EXAMPLE), Ok(10605));
+    assert_eq!(Day::solve_part_two(EXAMPLE), Ok(2713310158));
+  }
+
+  #[test]
+  fn test_malformed() {
+    let input = EXAMPLE.replacen("Starting items: 54, 65", "Starting items: 54, x5", 1);
+    let err = Day::parse(&input).map(|_| ()).unwrap_err();
+
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 9, column 23: invalid value `x5`: invalid digit found in string"
+    );
+    assert_eq!(
+      err.snippet().map(ToString::to_string).as_deref(),
+      Some("  |\n9 |   Starting items: 54, x5, 75, 74\n  |                       ^^")
+    );
+
+    let input = EXAMPLE.replacen("Test: divisible by 19", "Test: divisible by 0", 1);
+    let err = Day::parse(&input).map(|_| ()).unwrap_err();
+
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 11, column 9: can't divide by zero"
+    );
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve_part_one(INPUT), 69918);
-    assert_eq!(solve_part_two(INPUT), 19573408701);
+    aoc::answers::assert::<Day>(ANSWERS, 11, INPUT);
   }
 }
@@ -2,124 +2,132 @@
 //!
 //! [link]: https://adventofcode.com/2022/day/12
 
-use std::collections::VecDeque;
-use std::ops::Range;
-
-use aoc::Solution;
-
-const INPUT: &str = include_str!("input.txt");
-
-type Grid = Vec<Vec<u8>>;
-type Pair<T> = (T, T);
-
-fn bfs(grid: &[Vec<u8>], start: &[(usize, usize)], goal: (usize, usize)) -> Option<usize> {
-  let mut visited = vec![vec![false; grid[0].len()]; grid.len()];
-
-  let mut queue = start
-    .iter()
-    .map(|&(x, y)| (x, y, 0))
-    .collect::<VecDeque<_>>();
-
-  while let Some((x, y, len)) = queue.pop_front() {
-    if (x, y) == goal {
-      return Some(len);
-    }
-
-    for (dx, dy) in [(0, -1), (-1, 0), (0, 1), (1, 0)] {
-      let (nx, ny) = ((x as isize + dx) as usize, (y as isize + dy) as usize);
-      let Some(&square) = grid.get(nx).and_then(|row| row.get(ny)) else {
-        continue;
-      };
-
-      if grid[x][y] + 1 >= square && !visited[nx][ny] {
-        visited[nx][ny] = true;
-        queue.push_back((nx, ny, len + 1));
-      }
-    }
-  }
-
-  None
+use aoc::grid::{Grid, Position};
+use aoc::search::{self, Outcome};
+use aoc::{Error, Result, Solution, Solver};
+
+/// Shortest path from any of the starts to the goal, climbing at most one step up at a time.
+fn climb(
+  grid: &Grid<u8>,
+  starts: impl IntoIterator<Item = Position>,
+  goal: Position,
+) -> Outcome<Position> {
+  search::bfs(
+    starts,
+    |&position| {
+      grid
+        .neighbours(position)
+        .filter(move |&next| grid[position] + 1 >= grid[next])
+    },
+    |&position| position == goal,
+  )
 }
 
-fn parse_grid(input: &str) -> Grid {
-  input
-    .lines()
-    .map(|line| line.as_bytes().to_vec())
-    .collect::<Vec<_>>()
-}
+fn prepare_grid(mut grid: Grid<u8>) -> Result<(Grid<u8>, Position, Position)> {
+  // Find `S`tart position.
+  let start = grid
+    .find(|&square| square == b'S')
+    .ok_or_else(|| Error::parse("no start position `S`"))?;
 
-fn cartesian_product(rows: Range<usize>, cols: Range<usize>) -> impl Iterator<Item = Pair<usize>> {
-  rows.flat_map(move |row| cols.clone().map(move |col| (row, col)))
-}
+  // Find `E`nd position.
+  let end = grid
+    .find(|&square| square == b'E')
+    .ok_or_else(|| Error::parse("no end position `E`"))?;
 
-fn grid_sizes(grid: &Grid) -> Pair<Range<usize>> {
-  let rows = 0..grid.len();
-  let cols = 0..grid[0].len();
+  grid[start] = b'a';
+  grid[end] = b'z';
 
-  (rows, cols)
+  Ok((grid, start, end))
 }
 
-fn prepare_grid(mut grid: Grid) -> (Grid, Pair<usize>, Pair<usize>) {
-  let (rows, cols) = grid_sizes(&grid);
+struct Day;
 
-  // Find `S`tart position.
-  let (sx, sy) = cartesian_product(rows.clone(), cols.clone())
-    .find(|(x, y)| grid[*x][*y] == b'S')
-    .unwrap();
+impl Solver for Day {
+  /// Heightmap with the start and the end positions.
+  type Parsed = (Grid<u8>, Position, Position);
+  type PartOne = usize;
+  type PartTwo = usize;
 
-  // Find `E`nd position.
-  let (ex, ey) = cartesian_product(rows, cols)
-    .find(|(x, y)| grid[*x][*y] == b'E')
-    .unwrap();
-
-  grid[sx][sy] = b'a';
-  grid[ex][ey] = b'z';
+  const TITLE: &'static str = "Day 12: Hill Climbing Algorithm";
 
-  (grid, (sx, sy), (ex, ey))
-}
-
-fn solve_part_one(input: &str) -> usize {
-  let grid = parse_grid(input);
-  let (grid, start, end) = prepare_grid(grid);
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    let grid = Grid::parse(input, |square| {
+      match square {
+        | 'a'..='z' | 'S' | 'E' => Ok(square as u8),
+        | _ => Err(Error::parse(format!("invalid square `{square}`"))),
+      }
+    })?;
 
-  bfs(&grid, &[start], end).unwrap()
-}
+    prepare_grid(grid)
+  }
 
-fn solve_part_two(input: &str) -> usize {
-  let grid = parse_grid(input);
-  let (grid, _, (gx, gy)) = prepare_grid(grid);
-  let (rows, cols) = grid_sizes(&grid);
+  fn part_one((grid, start, end): &Self::Parsed) -> Result<Self::PartOne> {
+    climb(grid, [*start], *end)
+      .cost()
+      .ok_or_else(|| Error::no_solution("the end can't be reached"))
+  }
 
-  let positions = cartesian_product(rows, cols)
-    .filter(|&(x, y)| grid[x][y] == b'a')
-    .collect::<Vec<_>>();
+  fn part_two((grid, _, end): &Self::Parsed) -> Result<Self::PartTwo> {
+    let starts = grid
+      .iter()
+      .filter(|&(_, &square)| square == b'a')
+      .map(|(position, _)| position);
 
-  bfs(&grid, &positions, (gx, gy)).unwrap()
+    climb(grid, starts, *end)
+      .cost()
+      .ok_or_else(|| Error::no_solution("the end can't be reached"))
+  }
 }
 
-pub fn solution<'s>() -> Solution<'s, usize, usize> {
-  Solution {
-    title: "Day 12: Hill Climbing Algorithm",
-    part_one: solve_part_one(INPUT),
-    part_two: solve_part_two(INPUT),
-  }
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
   const EXAMPLE: &str = include_str!("example.txt");
 
   #[test]
   fn test_examples() {
-    assert_eq!(solve_part_one(EXAMPLE), 31);
-    assert_eq!(solve_part_two(EXAMPLE), 29);
+    assert_eq!(Day::solve_part_one(EXAMPLE), Ok(31));
+    assert_eq!(Day::solve_part_two(EXAMPLE), Ok(29));
+  }
+
+  #[test]
+  fn test_path() {
+    let (grid, start, end) = Day::parse(EXAMPLE).unwrap();
+    let outcome = climb(&grid, [start], end);
+    let path = outcome.path.unwrap();
+
+    assert_eq!(path.states.len(), 32);
+    assert_eq!(path.states.first(), Some(&start));
+    assert_eq!(path.states.last(), Some(&end));
+    assert!(path
+      .states
+      .windows(2)
+      .all(|pair| grid[pair[0]] + 1 >= grid[pair[1]]));
+    assert!(outcome.visited <= grid.width() * grid.height());
+  }
+
+  #[test]
+  fn test_malformed() {
+    for square in ['é', '{', 'A'] {
+      let input = EXAMPLE.replacen("yxx", &format!("y{square}x"), 1);
+      let err = Day::parse(&input).map(|_| ()).unwrap_err();
+
+      assert_eq!(
+        err.to_string(),
+        format!("invalid input at line 2, column 6: invalid square `{square}`")
+      );
+    }
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve_part_one(INPUT), 449);
-    assert_eq!(solve_part_two(INPUT), 443);
+    aoc::answers::assert::<Day>(ANSWERS, 12, INPUT);
   }
 }
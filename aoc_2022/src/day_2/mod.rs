@@ -4,16 +4,7 @@
 //!
 //! [link]: https://adventofcode.com/2022/day/2
 
-use aoc::{Part, Solution};
-
-const INPUT: &str = include_str!("input.txt");
-
-#[derive(Debug)]
-enum ParseError {
-  UnknownOutcome,
-  UnknownShape,
-  InvalidRound,
-}
+use aoc::{Error, Part, Result, Solution, Solver};
 
 #[derive(Debug, PartialEq)]
 enum Outcome {
@@ -23,14 +14,14 @@ enum Outcome {
 }
 
 impl TryFrom<&u8> for Outcome {
-  type Error = ParseError;
+  type Error = Error;
 
   fn try_from(s: &u8) -> Result<Self, Self::Error> {
     match *s as char {
       | 'X' => Ok(Outcome::Loss),
       | 'Y' => Ok(Outcome::Draw),
       | 'Z' => Ok(Outcome::Win),
-      | _ => Err(ParseError::UnknownOutcome),
+      | ch => Err(Error::parse(format!("unknown outcome `{ch}`"))),
     }
   }
 }
@@ -85,14 +76,14 @@ impl Shape {
 }
 
 impl TryFrom<&u8> for Shape {
-  type Error = ParseError;
+  type Error = Error;
 
   fn try_from(s: &u8) -> Result<Self, Self::Error> {
     match *s as char {
       | 'A' | 'X' => Ok(Self::Rock),
       | 'B' | 'Y' => Ok(Self::Paper),
       | 'C' | 'Z' => Ok(Self::Scissors),
-      | _ => Err(ParseError::UnknownShape),
+      | ch => Err(Error::parse(format!("unknown shape `{ch}`"))),
     }
   }
 }
@@ -103,31 +94,26 @@ struct Round {
 }
 
 impl Round {
-  fn try_parse_for(part: Part) -> impl Fn(&[u8]) -> Result<Self, ParseError> {
-    move |bytes| {
-      match bytes {
-        | [left, .., right] => {
-          let (shape, outcome) = match part {
-            | Part::One => {
-              let opponent = Shape::try_from(left)?;
-              let shape = Shape::try_from(right)?;
-              let outcome = opponent.to_outcome(&shape);
-
-              (shape, outcome)
-            },
-            | Part::Two => {
-              let outcome = Outcome::try_from(right)?;
-              let opponent = Shape::try_from(left)?;
-              let shape = opponent.with_outcome(&outcome);
-
-              (shape, outcome)
-            },
-          };
-
-          Ok(Round { shape, outcome })
+  fn try_parse_for(part: Part) -> impl Fn(&(u8, u8)) -> Result<Self> {
+    move |(left, right)| {
+      let (shape, outcome) = match part {
+        | Part::One => {
+          let opponent = Shape::try_from(left)?;
+          let shape = Shape::try_from(right)?;
+          let outcome = opponent.to_outcome(&shape);
+
+          (shape, outcome)
         },
-        | _ => Err(ParseError::InvalidRound),
-      }
+        | Part::Two => {
+          let outcome = Outcome::try_from(right)?;
+          let opponent = Shape::try_from(left)?;
+          let shape = opponent.with_outcome(&outcome);
+
+          (shape, outcome)
+        },
+      };
+
+      Ok(Round { shape, outcome })
     }
   }
 }
@@ -150,51 +136,72 @@ impl FromIterator<Round> for Output {
   }
 }
 
-fn solve_part_one(input: &str) -> usize {
-  let output = input
-    .lines()
-    .map(str::as_bytes)
-    .map(Round::try_parse_for(Part::One))
-    .map(Result::unwrap)
-    .collect::<Output>();
+fn score(rounds: &[(u8, u8)], part: Part) -> Result<usize> {
+  let output = rounds
+    .iter()
+    .map(Round::try_parse_for(part))
+    .collect::<Result<Output>>()?;
 
-  output.result
+  Ok(output.result)
 }
 
-fn solve_part_two(input: &str) -> usize {
-  let output = input
-    .lines()
-    .map(str::as_bytes)
-    .map(Round::try_parse_for(Part::Two))
-    .map(Result::unwrap)
-    .collect::<Output>();
+struct Day;
+
+impl Solver for Day {
+  /// Left and right columns of each round.
+  type Parsed = Vec<(u8, u8)>;
+  type PartOne = usize;
+  type PartTwo = usize;
+
+  const TITLE: &'static str = "Day 2: Rock Paper Scissors";
+
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    input
+      .lines()
+      .map(str::as_bytes)
+      .map(|bytes| {
+        match bytes {
+          | [left, .., right] => Ok((*left, *right)),
+          | _ => {
+            Err(Error::parse(format!(
+              "invalid round `{}`",
+              String::from_utf8_lossy(bytes)
+            )))
+          },
+        }
+      })
+      .collect()
+  }
 
-  output.result
-}
+  fn part_one(rounds: &Self::Parsed) -> Result<Self::PartOne> {
+    score(rounds, Part::One)
+  }
 
-pub fn solution<'s>() -> Solution<'s, usize, usize> {
-  Solution {
-    title: "Day 2: Rock Paper Scissors",
-    part_one: solve_part_one(INPUT),
-    part_two: solve_part_two(INPUT),
+  fn part_two(rounds: &Self::Parsed) -> Result<Self::PartTwo> {
+    score(rounds, Part::Two)
   }
 }
 
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
   const EXAMPLE: &str = include_str!("example.txt");
 
   #[test]
   fn test_examples() {
-    assert_eq!(solve_part_one(EXAMPLE), 15);
-    assert_eq!(solve_part_two(EXAMPLE), 12);
+    assert_eq!(Day::solve_part_one(EXAMPLE), Ok(15));
+    assert_eq!(Day::solve_part_two(EXAMPLE), Ok(12));
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve_part_one(INPUT), 11063);
-    assert_eq!(solve_part_two(INPUT), 10349);
+    aoc::answers::assert::<Day>(ANSWERS, 2, INPUT);
   }
 }
@@ -7,9 +7,7 @@
 use std::collections::{HashMap, HashSet};
 use std::ops::RangeInclusive;
 
-use aoc::Solution;
-
-const INPUT: &str = include_str!("input.txt");
+use aoc::{Error, Result, Solution, Solver};
 
 type CharSet = HashSet<char>;
 type PriorityMap = HashMap<char, u64>;
@@ -42,10 +40,11 @@ fn resolve_priority(ch: char, lc_map: &PriorityMap, uc_map: &PriorityMap) -> u64
   }
 }
 
-fn solve_part_one(s: &str) -> u64 {
+fn sum_misplaced(rucksacks: &[String]) -> u64 {
   let (lc_priorities, uc_priorities) = priorities();
 
-  s.lines()
+  rucksacks
+    .iter()
     .map(|line| {
       // Every line seems to has even length, so split in half is okay I guess...
       let (left, right) = line.split_at(line.len() / 2);
@@ -66,22 +65,20 @@ fn solve_part_one(s: &str) -> u64 {
     })
 }
 
-fn solve_part_two(s: &str) -> u64 {
+fn sum_badges(rucksacks: &[String]) -> u64 {
   let (lc_priorities, uc_priorities) = priorities();
 
-  let lines = s.lines().collect::<Vec<_>>();
-
-  lines
+  rucksacks
     // Split a vector of lines into chunks of 3 elements (elves).
     .chunks(3)
     // I guess it's better to panic instead, but w/e.
     .filter_map(|groups| {
       match *groups {
         // Man I freaking love slice patterns.
-        | [first, second, third] => {
+        | [ref first, ref second, ref third] => {
           vec![first, second, third]
             .into_iter()
-            .map(to_charset)
+            .map(|rucksack| to_charset(rucksack))
             // I'm not happy with `copied`, but w/e, it's `char`s being copied, so not a big deal.
             .reduce(|acc, set| acc.intersection(&set).copied().collect::<CharSet>())
             // This piece is cringeworthy as well.
@@ -101,29 +98,59 @@ fn solve_part_two(s: &str) -> u64 {
     })
 }
 
-pub fn solution<'s>() -> Solution<'s, u64, u64> {
-  Solution {
-    title: "Day 3: Rucksack Reorganization",
-    part_one: solve_part_one(INPUT),
-    part_two: solve_part_two(INPUT),
+struct Day;
+
+impl Solver for Day {
+  /// Rucksacks, one per line.
+  type Parsed = Vec<String>;
+  type PartOne = u64;
+  type PartTwo = u64;
+
+  const TITLE: &'static str = "Day 3: Rucksack Reorganization";
+
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    // Priorities are only defined for letters, so anything else is rejected upfront.
+    input
+      .lines()
+      .map(|line| {
+        if line.chars().all(|ch| ch.is_ascii_alphabetic()) {
+          Ok(line.to_string())
+        } else {
+          Err(Error::parse(format!("invalid rucksack `{line}`")))
+        }
+      })
+      .collect()
+  }
+
+  fn part_one(rucksacks: &Self::Parsed) -> Result<Self::PartOne> {
+    Ok(sum_misplaced(rucksacks))
   }
+
+  fn part_two(rucksacks: &Self::Parsed) -> Result<Self::PartTwo> {
+    Ok(sum_badges(rucksacks))
+  }
+}
+
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
   const EXAMPLE: &str = include_str!("example.txt");
 
   #[test]
   fn test_examples() {
-    assert_eq!(solve_part_one(EXAMPLE), 157);
-    assert_eq!(solve_part_two(EXAMPLE), 70);
+    assert_eq!(Day::solve_part_one(EXAMPLE), Ok(157));
+    assert_eq!(Day::solve_part_two(EXAMPLE), Ok(70));
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve_part_one(INPUT), 7446);
-    assert_eq!(solve_part_two(INPUT), 2646)
+    aoc::answers::assert::<Day>(ANSWERS, 3, INPUT);
   }
 }
@@ -2,99 +2,53 @@
 //!
 //! [link]: https://adventofcode.com/2022/day/4
 
-use std::ops::RangeInclusive;
 use std::str::FromStr;
 
-use aoc::{Part, Solution};
+use aoc::interval::Interval;
+use aoc::{Error, Part, Result, Solution, Solver};
 
-const INPUT: &str = include_str!("input.txt");
-
-trait SplittableTo {
-  /// Splits a given `&str` by `delimiter` into a pair of `T`.
-  fn split_to_pair<T>(s: &str, delimiter: &str) -> Result<(T, T), ParseError>
-  where
-    T: FromStr,
-  {
-    s.split_once(delimiter)
-      .and_then(|(first, second)| {
-        let first = first.parse::<T>().ok();
-        let second = second.parse::<T>().ok();
-
-        first.zip(second)
-      })
-      .ok_or(ParseError::InvalidPair)
-  }
-}
-
-#[derive(Debug)]
-enum ParseError {
-  InvalidAssignment,
-  InvalidPair,
-}
-
-struct Pair(usize, usize);
-
-impl SplittableTo for Pair {}
-
-impl Pair {
-  fn from_tuple((start, end): (usize, usize)) -> Pair {
-    Pair(start, end)
-  }
-
-  fn to_range_inc(&self) -> RangeInclusive<usize> {
-    self.0..=self.1
-  }
-
-  /// Checks if one range contains another range. Inclusive.
-  fn subsumes(&self, other: &Pair) -> bool {
-    let this = self.to_range_inc();
-    let that = other.to_range_inc();
-
-    let l_subsumes_r = this.contains(that.start()) && this.contains(that.end());
-    let r_subsumes_l = that.contains(this.start()) && that.contains(this.end());
-
-    l_subsumes_r || r_subsumes_l
-  }
-
-  /// Checks if one range overlaps (partially or as a whole) with anoterh range.
-  fn overlaps(&self, other: &Pair) -> bool {
-    let this = self.to_range_inc();
-    let that = other.to_range_inc();
-
-    let l_overlaps_r = this.contains(that.start()) || this.contains(that.end());
-    let r_overlaps_l = that.contains(this.start()) || that.contains(this.end());
-
-    l_overlaps_r || r_overlaps_l
-  }
-}
-
-impl FromStr for Pair {
-  type Err = ParseError;
-
-  fn from_str(s: &str) -> Result<Self, Self::Err> {
-    Pair::split_to_pair(s, "-")
-      .map(Pair::from_tuple)
-      .map_err(|_| ParseError::InvalidPair)
-  }
-}
-
-struct Assignment(Pair, Pair);
-
-impl SplittableTo for Assignment {}
+/// Sections assigned to a pair of elves, as inclusive ranges of section IDs.
+struct Assignment(Interval, Interval);
 
 impl Assignment {
-  fn from_tuple((first, second): (Pair, Pair)) -> Self {
-    Assignment(first, second)
+  /// Parses the `<first>-<last>` sections of an elf, a part of the assignment `line`.
+  fn sections(line: &str, sections: &str) -> Result<Interval> {
+    let bounds = sections
+      .split_once('-')
+      .and_then(|(first, last)| Some((first.parse::<i64>().ok()?, last.parse::<i64>().ok()?)));
+
+    match bounds {
+      | Some((first, last)) if first <= last => Ok(Interval::inclusive(first, last)),
+      | Some(_) => {
+        let message = format!("reversed sections `{sections}`");
+        Err(Error::at(
+          line,
+          sections,
+          message,
+          ["the first section up to the last one"],
+        ))
+      },
+      | None => {
+        let message = format!("invalid sections `{sections}`");
+        Err(Error::at(line, sections, message, ["`<first>-<last>`"]))
+      },
+    }
   }
 }
 
 impl FromStr for Assignment {
-  type Err = ParseError;
+  type Err = Error;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    Assignment::split_to_pair(s, ",")
-      .map(Assignment::from_tuple)
-      .map_err(|_| ParseError::InvalidAssignment)
+    let Some((first, second)) = s.split_once(',') else {
+      let message = format!("invalid assignment `{s}`");
+      return Err(Error::at(s, s, message, ["`<sections>,<sections>`"]));
+    };
+
+    Ok(Assignment(
+      Assignment::sections(s, first)?,
+      Assignment::sections(s, second)?,
+    ))
   }
 }
 
@@ -103,17 +57,17 @@ struct Output {
   result: usize,
 }
 
-impl FromIterator<(Part, Assignment)> for Output {
+impl<'a> FromIterator<(Part, &'a Assignment)> for Output {
   fn from_iter<I>(it: I) -> Self
   where
-    I: IntoIterator<Item = (Part, Assignment)>,
+    I: IntoIterator<Item = (Part, &'a Assignment)>,
   {
     it.into_iter().fold(
       Self::default(),
       |mut acc, (part, Assignment(first, second))| {
         let add = match part {
-          | Part::One => first.subsumes(&second),
-          | Part::Two => first.overlaps(&second),
+          | Part::One => first.covers(second) || second.covers(first),
+          | Part::Two => first.overlaps(second),
         };
 
         acc.result += add as usize;
@@ -123,51 +77,85 @@ impl FromIterator<(Part, Assignment)> for Output {
   }
 }
 
-fn solve_part_one(s: &str) -> usize {
-  let output = s
-    .lines()
-    .map(str::parse::<Assignment>)
-    .map(Result::unwrap)
-    .map(|assignment| (Part::One, assignment))
+fn count(assignments: &[Assignment], part: Part) -> usize {
+  let output = assignments
+    .iter()
+    .map(|assignment| (part, assignment))
     .collect::<Output>();
 
   output.result
 }
 
-fn solve_part_two(s: &str) -> usize {
-  let output = s
-    .lines()
-    .map(str::parse::<Assignment>)
-    .map(Result::unwrap)
-    .map(|assignment| (Part::Two, assignment))
-    .collect::<Output>();
+struct Day;
 
-  output.result
-}
+impl Solver for Day {
+  type Parsed = Vec<Assignment>;
+  type PartOne = usize;
+  type PartTwo = usize;
 
-pub fn solution<'s>() -> Solution<'s, usize, usize> {
-  Solution {
-    title: "Day 4: Camp Cleanup",
-    part_one: solve_part_one(INPUT),
-    part_two: solve_part_two(INPUT),
+  const TITLE: &'static str = "Day 4: Camp Cleanup";
+
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    input
+      .lines()
+      .enumerate()
+      .map(|(index, line)| {
+        line
+          .parse::<Assignment>()
+          .map_err(|err| err.on_line(index + 1))
+      })
+      .collect()
+  }
+
+  fn part_one(assignments: &Self::Parsed) -> Result<Self::PartOne> {
+    Ok(count(assignments, Part::One))
+  }
+
+  fn part_two(assignments: &Self::Parsed) -> Result<Self::PartTwo> {
+    Ok(count(assignments, Part::Two))
   }
 }
 
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
   const EXAMPLE: &str = include_str!("example.txt");
 
   #[test]
   fn test_examples() {
-    assert_eq!(solve_part_one(EXAMPLE), 2);
-    assert_eq!(solve_part_two(EXAMPLE), 4);
+    assert_eq!(Day::solve_part_one(EXAMPLE), Ok(2));
+    assert_eq!(Day::solve_part_two(EXAMPLE), Ok(4));
+  }
+
+  #[test]
+  fn test_malformed() {
+    let err = Day::parse("2-4,6-8\n2-3,5-4\n").map(|_| ()).unwrap_err();
+
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 2, column 5: reversed sections `5-4`"
+    );
+    assert_eq!(
+      err.snippet().map(ToString::to_string).as_deref(),
+      Some("  |\n2 | 2-3,5-4\n  |     ^^^ expected the first section up to the last one")
+    );
+
+    let err = Day::parse("2-4,6-x\n").map(|_| ()).unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 1, column 5: invalid sections `6-x`"
+    );
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve_part_one(INPUT), 536);
-    assert_eq!(solve_part_two(INPUT), 845);
+    aoc::answers::assert::<Day>(ANSWERS, 4, INPUT);
   }
 }
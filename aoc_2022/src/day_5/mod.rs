@@ -5,25 +5,28 @@
 use std::mem;
 use std::str;
 
-use aoc::Solution;
-
-const INPUT: &str = include_str!("input.txt");
+use aoc::diagnostic::Snippet;
+use aoc::{parse, Error, Result, Solution, Solver};
 
 /// Represents a stack of crates.
 type Stack = Vec<char>;
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 struct Crates {
   table: Vec<Stack>,
 }
 
 impl Crates {
-  fn from_line(mut crates: Crates, line: &str) -> Crates {
-    line
+  fn from_line(mut crates: Crates, line: &str) -> Result<Crates> {
+    let chunks = line
       .as_bytes()
       .chunks(4)
       .map(str::from_utf8)
-      .map(Result::unwrap)
+      .collect::<Result<Vec<_>, _>>()
+      .map_err(|_| Error::parse(format!("invalid crates `{line}`")))?;
+
+    chunks
+      .into_iter()
       .map(|chunk| {
         chunk
           .trim()
@@ -40,43 +43,62 @@ impl Crates {
         crates.table[index].extend(value)
       });
 
-    crates
+    Ok(crates)
   }
 
-  fn run(&mut self, cmd: Move) -> &mut Self {
-    let Move { from, to, stack } = cmd;
+  /// Converts 1-based stack numbers of a move into indices, checking that they exist and that
+  /// there are enough crates to move. Errors point at the line of the move.
+  fn indices(&self, cmd: &Move) -> Result<(usize, usize)> {
+    let index = |stack: usize| {
+      stack
+        .checked_sub(1)
+        .filter(|index| *index < self.table.len())
+        .ok_or_else(|| Error::parse(format!("there is no stack {stack}")).on_line(cmd.line))
+    };
+
+    let (from, to) = (index(cmd.from)?, index(cmd.to)?);
+    let len = self.table[from].len();
+
+    if cmd.stack > len {
+      let message = format!(
+        "can't move {} crates from stack {}, it has only {len}",
+        cmd.stack, cmd.from
+      );
+
+      return Err(Error::parse(message).on_line(cmd.line));
+    }
+
+    Ok((from, to))
+  }
 
-    let from = from - 1;
-    let to = to - 1;
+  fn run(&mut self, cmd: Move) -> Result<&mut Self> {
+    let (from, to) = self.indices(&cmd)?;
 
-    for _ in 0..stack {
+    for _ in 0..cmd.stack {
       let elem = self.table[from].pop();
       self.table[to].extend(elem)
     }
 
-    self
+    Ok(self)
   }
 
-  fn run_preserving(&mut self, cmd: Move) -> &mut Self {
-    let Move { from, to, stack } = cmd;
-
-    let from = from - 1;
-    let to = to - 1;
+  fn run_preserving(&mut self, cmd: Move) -> Result<&mut Self> {
+    let (from, to) = self.indices(&cmd)?;
 
     let mut next_stack = mem::take(&mut self.table[to]);
 
     let crates = {
       let current_stack = &mut self.table[from];
-      let drain_range = (current_stack.len() - stack)..;
+      let start = current_stack.len() - cmd.stack;
 
-      current_stack.drain(drain_range)
+      current_stack.drain(start..)
     };
 
     next_stack.extend(crates);
 
     self.table[to] = next_stack;
 
-    self
+    Ok(self)
   }
 
   fn top(&self) -> String {
@@ -84,89 +106,133 @@ impl Crates {
   }
 }
 
+#[derive(Clone, Copy)]
 struct Move {
   stack: usize,
   from: usize,
   to: usize,
+  /// Line of the move in the input, for errors found only when it's run.
+  line: usize,
 }
 
 impl Move {
-  fn from_line(line: &str) -> Move {
+  fn from_line(line: &str, number: usize) -> Result<Move> {
     let values: Vec<_> = line.split_whitespace().collect();
 
     if let ["move", stack, "from", from, "to", to] = *values.as_slice() {
-      let [stack, from, to] = [stack, from, to]
-        .map(str::parse::<usize>)
-        .map(Result::unwrap);
+      let [stack, from, to] = [stack, from, to].map(str::parse::<usize>);
 
-      Move { stack, from, to }
+      Ok(Move {
+        stack: stack?,
+        from: from?,
+        to: to?,
+        line: number,
+      })
     } else {
-      panic!("Couldn't parse the move command.")
+      Err(Error::parse(format!("invalid move command `{line}`")).on_line(number))
     }
   }
 }
 
-fn solve_common(s: &str) -> (Crates, Vec<Move>) {
-  let mut lines = s.lines();
+/// Starting stacks of crates and the rearrangement procedure.
+struct Procedure {
+  crates: Crates,
+  moves: Vec<Move>,
+}
+
+struct Day;
 
-  let mut crates = lines
-    .by_ref()
-    .take_while(|s| !s.is_empty())
-    .fold(Crates::default(), Crates::from_line);
+impl Solver for Day {
+  type Parsed = Procedure;
+  type PartOne = String;
+  type PartTwo = String;
 
-  crates.table.iter_mut().for_each(|stack| stack.reverse());
+  const TITLE: &'static str = "Day 5: Supply Stacks";
 
-  let moves = lines
-    .filter(|s| !s.is_empty())
-    .map(Move::from_line)
-    .collect::<Vec<_>>();
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    let mut paragraphs = parse::paragraphs(input);
 
-  (crates, moves)
+    let mut crates = paragraphs
+      .next()
+      .unwrap_or_default()
+      .lines()
+      .try_fold(Crates::default(), Crates::from_line)?;
 
-  // match P {
-  //   | Part::One => it.by_ref().fold(&mut crates, Crates::run).top(),
-  //   | Part::Two => it.by_ref().fold(&mut crates, Crates::run_preserving).top(),
-  // }
-}
+    crates.table.iter_mut().for_each(|stack| stack.reverse());
 
-fn solve_part_one(s: &str) -> String {
-  let (mut crates, moves) = solve_common(s);
+    let moves = paragraphs
+      .flat_map(str::lines)
+      .map(|line| Move::from_line(line, Snippet::of(input, line).location().line))
+      .collect::<Result<Vec<_>>>()?;
 
-  moves.into_iter().fold(&mut crates, Crates::run).top()
-}
+    Ok(Procedure { crates, moves })
+  }
 
-fn solve_part_two(s: &str) -> String {
-  let (mut crates, moves) = solve_common(s);
+  fn part_one(procedure: &Self::Parsed) -> Result<Self::PartOne> {
+    let mut crates = procedure.crates.clone();
 
-  moves
-    .into_iter()
-    .fold(&mut crates, Crates::run_preserving)
-    .top()
-}
+    procedure
+      .moves
+      .iter()
+      .copied()
+      .try_fold(&mut crates, Crates::run)
+      .map(|crates| crates.top())
+  }
 
-pub fn solution<'s>() -> Solution<'s, String, String> {
-  Solution {
-    title: "Day 5: Supply Stacks",
-    part_one: solve_part_one(INPUT),
-    part_two: solve_part_two(INPUT),
+  fn part_two(procedure: &Self::Parsed) -> Result<Self::PartTwo> {
+    let mut crates = procedure.crates.clone();
+
+    procedure
+      .moves
+      .iter()
+      .copied()
+      .try_fold(&mut crates, Crates::run_preserving)
+      .map(|crates| crates.top())
   }
 }
 
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
   const EXAMPLE: &str = include_str!("example.txt");
 
   #[test]
   fn test_examples() {
-    assert_eq!(solve_part_one(EXAMPLE), "CMZ".to_string());
-    assert_eq!(solve_part_two(EXAMPLE), "MCD".to_string());
+    assert_eq!(Day::solve_part_one(EXAMPLE), Ok("CMZ".to_string()));
+    assert_eq!(Day::solve_part_two(EXAMPLE), Ok("MCD".to_string()));
+  }
+
+  #[test]
+  fn test_malformed() {
+    let input = EXAMPLE.replacen("move 3 from 1 to 3", "move 4 from 1 to 3", 1);
+    let message =
+      "invalid input at line 7, column 1: can't move 4 crates from stack 1, it has only 3";
+
+    assert_eq!(
+      Day::solve_part_one(&input).unwrap_err().to_string(),
+      message
+    );
+    assert_eq!(
+      Day::solve_part_two(&input).unwrap_err().to_string(),
+      message
+    );
+
+    let input = EXAMPLE.replacen("move 1 from 1 to 2", "move 1 from 4 to 2", 1);
+    assert_eq!(
+      Day::solve_part_one(&input).unwrap_err().to_string(),
+      "invalid input at line 9, column 1: there is no stack 4"
+    );
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve_part_one(INPUT), "ZRLJGSCTR".to_string());
-    assert_eq!(solve_part_two(INPUT), "PRTTGRFPB".to_string());
+    aoc::answers::assert::<Day>(ANSWERS, 5, INPUT);
   }
 }
@@ -2,50 +2,84 @@
 //!
 //! [link]: https://adventofcode.com/2022/day/6
 
-use aoc::Solution;
+use aoc::{Error, Result, Solution, Solver};
 
-const INPUT: &str = include_str!("input.txt");
-
-fn solve<const N: usize>(s: &str) -> usize {
-  s.as_bytes()
+fn find_marker<const N: usize>(stream: &[u8]) -> Result<usize> {
+  stream
     .windows(N)
     .position(|chunk| (1..chunk.len()).all(|cursor| !chunk[cursor..].contains(&chunk[cursor - 1])))
     .map(|pos| pos + N)
-    .unwrap_or_default()
+    .ok_or_else(|| Error::no_solution(format!("no {N} distinct characters in a row")))
 }
 
-pub fn solution<'s>() -> Solution<'s, usize, usize> {
-  Solution {
-    title: "Day 6: Tuning Trouble",
-    part_one: solve::<4>(INPUT),
-    part_two: solve::<14>(INPUT),
+struct Day;
+
+impl Solver for Day {
+  /// Datastream buffer.
+  type Parsed = Vec<u8>;
+  type PartOne = usize;
+  type PartTwo = usize;
+
+  const TITLE: &'static str = "Day 6: Tuning Trouble";
+
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    Ok(input.as_bytes().to_vec())
+  }
+
+  fn part_one(stream: &Self::Parsed) -> Result<Self::PartOne> {
+    find_marker::<4>(stream)
+  }
+
+  fn part_two(stream: &Self::Parsed) -> Result<Self::PartTwo> {
+    find_marker::<14>(stream)
   }
 }
 
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
+
   #[test]
   fn test_examples() {
     // Part 1.
-    assert_eq!(solve::<4>("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 7);
-    assert_eq!(solve::<4>("bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
-    assert_eq!(solve::<4>("nppdvjthqldpwncqszvftbrmjlhg"), 6);
-    assert_eq!(solve::<4>("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 10);
-    assert_eq!(solve::<4>("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 11);
+    assert_eq!(Day::solve_part_one("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Ok(7));
+    assert_eq!(Day::solve_part_one("bvwbjplbgvbhsrlpgdmjqwftvncz"), Ok(5));
+    assert_eq!(Day::solve_part_one("nppdvjthqldpwncqszvftbrmjlhg"), Ok(6));
+    assert_eq!(
+      Day::solve_part_one("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"),
+      Ok(10)
+    );
+    assert_eq!(
+      Day::solve_part_one("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"),
+      Ok(11)
+    );
 
     // Part 2.
-    assert_eq!(solve::<14>("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 19);
-    assert_eq!(solve::<14>("bvwbjplbgvbhsrlpgdmjqwftvncz"), 23);
-    assert_eq!(solve::<14>("nppdvjthqldpwncqszvftbrmjlhg"), 23);
-    assert_eq!(solve::<14>("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 29);
-    assert_eq!(solve::<14>("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 26);
+    assert_eq!(
+      Day::solve_part_two("mjqjpqmgbljsphdztnvjfqwrcgsmlb"),
+      Ok(19)
+    );
+    assert_eq!(Day::solve_part_two("bvwbjplbgvbhsrlpgdmjqwftvncz"), Ok(23));
+    assert_eq!(Day::solve_part_two("nppdvjthqldpwncqszvftbrmjlhg"), Ok(23));
+    assert_eq!(
+      Day::solve_part_two("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"),
+      Ok(29)
+    );
+    assert_eq!(
+      Day::solve_part_two("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"),
+      Ok(26)
+    );
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve::<4>(INPUT), 1850);
-    assert_eq!(solve::<14>(INPUT), 2823);
+    aoc::answers::assert::<Day>(ANSWERS, 6, INPUT);
   }
 }
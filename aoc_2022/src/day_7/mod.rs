@@ -5,11 +5,9 @@
 use std::path::PathBuf;
 use std::{collections::HashMap, path::Path};
 
-use aoc::Solution;
+use aoc::{Error, Result, Solution, Solver};
 
-const INPUT: &str = include_str!("input.txt");
-
-fn collect_sizes(input: &str) -> HashMap<PathBuf, usize> {
+fn collect_sizes(input: &str) -> Result<HashMap<PathBuf, usize>> {
   let mut sizes = HashMap::<PathBuf, usize>::new();
   let mut paths = Vec::new();
 
@@ -28,7 +26,7 @@ fn collect_sizes(input: &str) -> HashMap<PathBuf, usize> {
         paths.push(name);
       },
       | [size, _] => {
-        let size = size.parse::<usize>().unwrap();
+        let size = size.parse::<usize>()?;
 
         for index in 0..paths.len() {
           let path = PathBuf::from_iter(&paths[..=index]);
@@ -39,56 +37,70 @@ fn collect_sizes(input: &str) -> HashMap<PathBuf, usize> {
     };
   }
 
-  sizes
+  Ok(sizes)
 }
 
-fn solve_part_one(input: &str) -> usize {
-  const SIZE_LIMIT: usize = 100_000;
+struct Day;
 
-  let sizes = collect_sizes(input);
+impl Solver for Day {
+  /// Total size of each directory.
+  type Parsed = HashMap<PathBuf, usize>;
+  type PartOne = usize;
+  type PartTwo = usize;
 
-  sizes.into_values().filter(|size| *size <= SIZE_LIMIT).sum()
-}
+  const TITLE: &'static str = "Day 7: No Space Left On Device";
 
-fn solve_part_two(input: &str) -> usize {
-  const DISK_SPACE: usize = 70_000_000;
-  const UNUSED_SPACE: usize = 30_000_000;
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    collect_sizes(input)
+  }
 
-  let sizes = collect_sizes(input);
+  fn part_one(sizes: &Self::Parsed) -> Result<Self::PartOne> {
+    const SIZE_LIMIT: usize = 100_000;
 
-  let root = sizes.get(Path::new("/")).unwrap();
-  let available = DISK_SPACE - root;
+    Ok(sizes.values().filter(|size| **size <= SIZE_LIMIT).sum())
+  }
 
-  sizes
-    .into_values()
-    .filter(|size| (available + size) >= UNUSED_SPACE)
-    .min()
-    .unwrap_or_default()
-}
+  fn part_two(sizes: &Self::Parsed) -> Result<Self::PartTwo> {
+    const DISK_SPACE: usize = 70_000_000;
+    const UNUSED_SPACE: usize = 30_000_000;
 
-pub fn solution<'s>() -> Solution<'s, usize, usize> {
-  Solution {
-    title: "Day 7: No Space Left On Device",
-    part_one: solve_part_one(INPUT),
-    part_two: solve_part_two(INPUT),
+    let root = sizes
+      .get(Path::new("/"))
+      .ok_or_else(|| Error::parse("the root directory is never entered"))?;
+
+    let available = DISK_SPACE
+      .checked_sub(*root)
+      .ok_or_else(|| Error::parse(format!("files take {root}, more than the disk space")))?;
+
+    sizes
+      .values()
+      .copied()
+      .filter(|size| (available + size) >= UNUSED_SPACE)
+      .min()
+      .ok_or_else(|| Error::no_solution("no directory frees up enough space"))
   }
 }
 
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
   const EXAMPLE: &str = include_str!("example.txt");
 
   #[test]
   fn test_examples() {
-    assert_eq!(solve_part_one(EXAMPLE), 95437);
-    assert_eq!(solve_part_two(EXAMPLE), 24933642);
+    assert_eq!(Day::solve_part_one(EXAMPLE), Ok(95437));
+    assert_eq!(Day::solve_part_two(EXAMPLE), Ok(24933642));
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve_part_one(INPUT), 2104783);
-    assert_eq!(solve_part_two(INPUT), 5883165);
+    aoc::answers::assert::<Day>(ANSWERS, 7, INPUT);
   }
 }
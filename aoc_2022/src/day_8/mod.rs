@@ -1,195 +1,99 @@
 //! [Day 8: Treetop Tree House][link]
 //!
-//! Playing with declarative macros, because why not. The overall perf and algo complexity is kinda
-//! nasty, something like `O(n^3)`, but it's whatever, since it does the job in 3-4 ms on the big
-//! puzzle input.
+//! Every tree looks along the four rays of the grid towards the edges, which makes it `O(n^3)`,
+//! but it does the job in a few ms on the big puzzle input.
 //!
 //! [link]: https://adventofcode.com/2022/day/8
 
-use aoc::Solution;
+use aoc::grid::{Grid, Position, ORTHOGONAL};
+use aoc::{Error, Result, Solution, Solver};
 
-const INPUT: &str = include_str!("input.txt");
+fn is_visible(grid: &Grid<u32>, position: Position) -> bool {
+  let height = grid[position];
 
-/// Simple macro for returning given result early if condition passes. Somewhat useful.
-macro_rules! return_early_if {
-  ($cond:expr, $result:expr) => {{
-    if $cond {
-      return $result;
-    }
-  }};
+  ORTHOGONAL
+    .into_iter()
+    .any(|offset| grid.ray(position, offset).all(|(_, &other)| other < height))
 }
 
-/// Another simple macro for continuing if condition passes. Very useful. /s
-macro_rules! continue_if {
-  ($cond:expr) => {
-    if $cond {
-      continue;
-    }
-  };
-}
-
-type Grid = Vec<Vec<usize>>;
-type Pair<T> = (T, T);
-
-fn parse_line(line: &str) -> Vec<usize> {
-  line
-    .chars()
-    .map(|ch| ch.to_digit(10).unwrap_or(0) as usize)
-    .collect()
-}
-
-fn prepare(input: &str) -> (Grid, usize, usize, usize) {
-  let result = 0;
-
-  // Build the grid.
-  let grid = input.lines().map(parse_line).collect::<Grid>();
-
-  // How much rows and cols? Will be needed later.
-  let rows = grid.len();
-  let cols = grid.first().map(Vec::len).unwrap_or(0);
-
-  (grid, result, rows, cols)
-}
-
-fn check_visibility(grid: &Grid, height: usize, pos: Pair<usize>, bounds: Pair<usize>) -> bool {
-  let (row, col) = pos;
-  let (rows, cols) = bounds;
+fn scenic_score(grid: &Grid<u32>, position: Position) -> usize {
+  let height = grid[position];
 
-  // Check the trees above the current one.
-  return_early_if!((0..row).all(|x| grid[x][col] < height), true);
+  ORTHOGONAL
+    .into_iter()
+    .map(|offset| {
+      let mut distance = 0;
 
-  // Check the trees to the left of the current one.
-  return_early_if!((0..col).all(|x| grid[row][x] < height), true);
+      // Trees are counted up to and including the first one blocking the view.
+      for (_, &other) in grid.ray(position, offset) {
+        distance += 1;
 
-  // Check the trees to the right of the current one.
-  return_early_if!((col + 1..cols).all(|x| grid[row][x] < height), true);
-
-  // Check the trees below the current one.
-  return_early_if!((row + 1..rows).all(|x| grid[x][col] < height), true);
+        if other >= height {
+          break;
+        }
+      }
 
-  false
+      distance
+    })
+    .product()
 }
 
-fn scenic_score(grid: &Grid, height: usize, pos: Pair<usize>, bounds: Pair<usize>) -> usize {
-  let (row, col) = pos;
-  let (rows, cols) = bounds;
-
-  let mut top = 0;
-  let mut bottom = 0;
-  let mut left = 0;
-  let mut right = 0;
-
-  for k in (0..row).rev() {
-    top += 1;
-
-    if grid[k][col] >= height {
-      break;
-    }
-  }
-
-  for k in (0..col).rev() {
-    left += 1;
-
-    if grid[row][k] >= height {
-      break;
-    }
-  }
-
-  #[allow(clippy::needless_range_loop)]
-  for k in row + 1..rows {
-    bottom += 1;
+struct Day;
 
-    if grid[k][col] >= height {
-      break;
-    }
-  }
+impl Solver for Day {
+  type Parsed = Grid<u32>;
+  type PartOne = usize;
+  type PartTwo = usize;
 
-  for k in col + 1..cols {
-    right += 1;
+  const TITLE: &'static str = "Day 8: Treetop Tree House";
 
-    if grid[row][k] >= height {
-      break;
-    }
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    Grid::parse(input, |ch| {
+      ch.to_digit(10)
+        .ok_or_else(|| Error::parse(format!("invalid tree height `{ch}`")))
+    })
   }
 
-  left * right * top * bottom
-}
-
-fn solve_part_one(input: &str) -> usize {
-  let (grid, mut result, rows, cols) = prepare(input);
-
-  for row in 0..rows {
-    // Skip the first and the last rows, since they are always visible.
-    continue_if!(row == 0 || row == rows - 1);
-
-    // All rows have the same amount of cols.
-    for col in 0..cols {
-      // Also skip the first and the last cols.
-      continue_if!(col == 0 || col == cols - 1);
-
-      // Height of the current tree.
-      let height = grid[row][col];
-
-      result += check_visibility(&grid, height, (row, col), (rows, cols)) as usize;
-    }
+  fn part_one(grid: &Self::Parsed) -> Result<Self::PartOne> {
+    Ok(
+      grid
+        .positions()
+        .filter(|&position| is_visible(grid, position))
+        .count(),
+    )
   }
 
-  // Calculate amount of trees on the edges to add to the result.
-  let edges = 2 * (rows + cols) - 4;
-
-  result + edges
-}
-
-fn solve_part_two(input: &str) -> usize {
-  let (grid, mut result, rows, cols) = prepare(input);
-
-  for row in 0..rows {
-    // Skip the first and the last rows, since they are always visible.
-    continue_if!(row == 0 || row == rows - 1);
-
-    // All rows have the same amount of cols.
-    for col in 0..cols {
-      // Also skip the first and the last cols.
-      continue_if!(col == 0 || col == cols - 1);
-
-      // Height of the current tree.
-      let height = grid[row][col];
-
-      // Calculate scenic score for visible trees.
-      let score = scenic_score(&grid, height, (row, col), (rows, cols));
-
-      if score > result {
-        result = score
-      }
-    }
+  fn part_two(grid: &Self::Parsed) -> Result<Self::PartTwo> {
+    Ok(
+      grid
+        .positions()
+        .map(|position| scenic_score(grid, position))
+        .max()
+        .unwrap_or(0),
+    )
   }
-
-  result
 }
 
-pub fn solution<'s>() -> Solution<'s, usize, usize> {
-  Solution {
-    title: "Day 8: Treetop Tree House",
-    part_one: solve_part_one(INPUT),
-    part_two: solve_part_two(INPUT),
-  }
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
   const EXAMPLE: &str = include_str!("example.txt");
 
   #[test]
   fn test_examples() {
-    assert_eq!(solve_part_one(EXAMPLE), 21);
-    assert_eq!(solve_part_two(EXAMPLE), 8);
+    assert_eq!(Day::solve_part_one(EXAMPLE), Ok(21));
+    assert_eq!(Day::solve_part_two(EXAMPLE), Ok(8));
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve_part_one(INPUT), 1703);
-    assert_eq!(solve_part_two(INPUT), 496650);
+    aoc::answers::assert::<Day>(ANSWERS, 8, INPUT);
   }
 }
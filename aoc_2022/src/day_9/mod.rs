@@ -8,120 +8,134 @@
 use std::collections::HashSet;
 use std::str::FromStr;
 
-use aoc::Solution;
+use aoc::geometry::{Direction, Point};
+use aoc::{Error, Result, Solution, Solver};
 
-const INPUT: &str = include_str!("input.txt");
-
-type Coord = (isize, isize);
-
-#[derive(Debug)]
-enum ParseError {
-  Move,
-  Steps,
-  Direction,
-}
-
-enum Move {
-  Up(usize),
-  Down(usize),
-  Left(usize),
-  Right(usize),
-}
-
-impl Move {
-  fn to_coords(&self) -> Vec<Coord> {
-    match *self {
-      | Move::Up(steps) => vec![(0, 1); steps],
-      | Move::Down(steps) => vec![(0, -1); steps],
-      | Move::Left(steps) => vec![(-1, 0); steps],
-      | Move::Right(steps) => vec![(1, 0); steps],
-    }
-  }
+struct Move {
+  direction: Direction,
+  steps: usize,
 }
 
 impl FromStr for Move {
-  type Err = ParseError;
+  type Err = Error;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
     match s.trim().split_once(' ') {
       | Some((direction, steps)) => {
-        let steps = steps.parse::<usize>().map_err(|_| ParseError::Steps)?;
-
-        let move_ = match direction {
-          | "U" => Move::Up(steps),
-          | "D" => Move::Down(steps),
-          | "L" => Move::Left(steps),
-          | "R" => Move::Right(steps),
-          | _ => return Err(ParseError::Direction),
-        };
-
-        Ok(move_)
+        let steps = steps
+          .parse::<usize>()
+          .map_err(|_| Error::at(s, steps, format!("invalid steps `{steps}`"), &["a number"]))?;
+
+        match direction.parse::<Direction>() {
+          | Ok(direction) if !direction.is_diagonal() => Ok(Move { direction, steps }),
+          | _ => {
+            let message = format!("invalid direction `{direction}`");
+            Err(Error::at(
+              s,
+              direction,
+              message,
+              &["`U`", "`D`", "`L`", "`R`"],
+            ))
+          },
+        }
+      },
+      | None => {
+        Err(Error::at(
+          s,
+          s,
+          format!("invalid move `{s}`"),
+          &["`<direction> <steps>`"],
+        ))
       },
-      | None => Err(ParseError::Move),
     }
   }
 }
 
 fn process(moves: &[Move], rope_len: usize) -> usize {
-  let mut rope: Vec<Coord> = vec![(0, 0); rope_len];
-  let mut visited: HashSet<Coord> = HashSet::from([(0, 0)]);
+  let mut rope = vec![Point::ORIGIN; rope_len];
+  let mut visited = HashSet::from([Point::ORIGIN]);
 
-  for (mx, my) in moves.iter().flat_map(Move::to_coords) {
-    rope[0].0 += mx;
-    rope[0].1 += my;
+  for Move { direction, steps } in moves {
+    for _ in 0..*steps {
+      rope[0] += direction.vector();
 
-    for index in 1..rope_len {
-      let dx = rope[index - 1].0 - rope[index].0;
-      let dy = rope[index - 1].1 - rope[index].1;
+      for index in 1..rope_len {
+        let gap = rope[index - 1] - rope[index];
 
-      if dx.abs() < 2 && dy.abs() < 2 {
-        continue;
+        // Knots only move once they stop touching the one ahead of them.
+        if gap.chebyshev() > 1 {
+          rope[index] += gap.signum();
+        }
       }
 
-      dx.ne(&0).then(|| rope[index].0 += dx.signum());
-      dy.ne(&0).then(|| rope[index].1 += dy.signum());
+      visited.insert(rope[rope_len - 1]);
     }
-
-    visited.insert(rope[rope_len - 1]);
   }
 
   visited.len()
 }
 
-fn solve<const N: usize>(input: &str) -> usize {
-  let moves = input
-    .lines()
-    .map(str::parse::<Move>)
-    .map(Result::unwrap)
-    .collect::<Vec<Move>>();
+struct Day;
 
-  process(&moves, N)
-}
+impl Solver for Day {
+  type Parsed = Vec<Move>;
+  type PartOne = usize;
+  type PartTwo = usize;
+
+  const TITLE: &'static str = "Day 9: Rope Bridge";
+
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    input
+      .lines()
+      .enumerate()
+      .map(|(index, line)| line.parse::<Move>().map_err(|err| err.on_line(index + 1)))
+      .collect()
+  }
+
+  fn part_one(moves: &Self::Parsed) -> Result<Self::PartOne> {
+    Ok(process(moves, 2))
+  }
 
-pub fn solution<'s>() -> Solution<'s, usize, usize> {
-  Solution {
-    title: "Day 9: Rope Bridge",
-    part_one: solve::<2>(INPUT),
-    part_two: solve::<10>(INPUT),
+  fn part_two(moves: &Self::Parsed) -> Result<Self::PartTwo> {
+    Ok(process(moves, 10))
   }
 }
 
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
   const EXAMPLE_ONE: &str = include_str!("example_one.txt");
   const EXAMPLE_TWO: &str = include_str!("example_two.txt");
 
   #[test]
   fn test_examples() {
-    assert_eq!(solve::<2>(EXAMPLE_ONE), 13);
-    assert_eq!(solve::<10>(EXAMPLE_TWO), 36);
+    assert_eq!(Day::solve_part_one(EXAMPLE_ONE), Ok(13));
+    assert_eq!(Day::solve_part_two(EXAMPLE_TWO), Ok(36));
+  }
+
+  #[test]
+  fn test_malformed() {
+    let err = Day::parse("R 4\nU x4\n").map(|_| ()).unwrap_err();
+
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 2, column 3: invalid steps `x4`"
+    );
+    assert_eq!(
+      err.snippet().map(ToString::to_string).as_deref(),
+      Some("  |\n2 | U x4\n  |   ^^ expected a number")
+    );
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve::<2>(INPUT), 6057);
-    assert_eq!(solve::<10>(INPUT), 2514);
+    aoc::answers::assert::<Day>(ANSWERS, 9, INPUT);
   }
 }
@@ -4,24 +4,28 @@
 
 use std::collections::HashMap;
 
-use aoc::Solution;
+use aoc::{Error, Result, Solution, Solver};
 
-const INPUT: &str = include_str!("input.txt");
+fn no_digits(line: &str) -> Error {
+  Error::no_solution(format!("no digits in `{line}`"))
+}
 
-fn solve_part_one(input: &str) -> u32 {
+fn sum_digits(lines: &[String]) -> Result<u32> {
   let mut result = 0;
 
-  for line in input.lines() {
-    let first = line.chars().find_map(|ch| ch.to_digit(10)).unwrap();
-    let last = line.chars().rev().find_map(|ch| ch.to_digit(10)).unwrap();
+  for line in lines {
+    let first = line.chars().find_map(|ch| ch.to_digit(10));
+    let last = line.chars().rev().find_map(|ch| ch.to_digit(10));
+
+    let (first, last) = first.zip(last).ok_or_else(|| no_digits(line))?;
 
     result += first * 10 + last;
   }
 
-  result
+  Ok(result)
 }
 
-fn solve_part_two(input: &str) -> u32 {
+fn sum_spelled_digits(lines: &[String]) -> Result<u32> {
   let mappings = HashMap::from([
     ("1", 1),
     ("2", 2),
@@ -47,9 +51,9 @@ fn solve_part_two(input: &str) -> u32 {
 
   let mut result = 0;
 
-  for line in input.lines() {
-    let mut forwards = line;
-    let mut backwards = line;
+  for line in lines {
+    let mut forwards = line.as_str();
+    let mut backwards = line.as_str();
 
     let first = 'outer: loop {
       for (prefix, num) in mappings.iter() {
@@ -58,7 +62,9 @@ fn solve_part_two(input: &str) -> u32 {
         }
       }
 
-      forwards = &forwards[1..];
+      let mut chars = forwards.chars();
+      chars.next().ok_or_else(|| no_digits(line))?;
+      forwards = chars.as_str();
     };
 
     let last = 'outer: loop {
@@ -68,39 +74,61 @@ fn solve_part_two(input: &str) -> u32 {
         }
       }
 
-      backwards = &backwards[..backwards.len() - 1];
+      let mut chars = backwards.chars();
+      chars.next_back().ok_or_else(|| no_digits(line))?;
+      backwards = chars.as_str();
     };
 
     result += first * 10 + last;
   }
 
-  result
+  Ok(result)
 }
 
-pub fn solution<'s>() -> Solution<'s, u32, u32> {
-  Solution {
-    title: "Day 1: Trebuchet?!",
-    part_one: solve_part_one(INPUT),
-    part_two: solve_part_two(INPUT),
+struct Day;
+
+impl Solver for Day {
+  /// Calibration document lines.
+  type Parsed = Vec<String>;
+  type PartOne = u32;
+  type PartTwo = u32;
+
+  const TITLE: &'static str = "Day 1: Trebuchet?!";
+
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    Ok(input.lines().map(str::to_string).collect())
   }
+
+  fn part_one(lines: &Self::Parsed) -> Result<Self::PartOne> {
+    sum_digits(lines)
+  }
+
+  fn part_two(lines: &Self::Parsed) -> Result<Self::PartTwo> {
+    sum_spelled_digits(lines)
+  }
+}
+
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
   const EXAMPLE_1: &str = include_str!("example-1.txt");
   const EXAMPLE_2: &str = include_str!("example-2.txt");
 
   #[test]
   fn test_examples() {
-    assert_eq!(solve_part_one(EXAMPLE_1), 142);
-    assert_eq!(solve_part_two(EXAMPLE_2), 281);
+    assert_eq!(Day::solve_part_one(EXAMPLE_1), Ok(142));
+    assert_eq!(Day::solve_part_two(EXAMPLE_2), Ok(281));
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve_part_one(INPUT), 54968);
-    assert_eq!(solve_part_two(INPUT), 54094);
+    aoc::answers::assert::<Day>(ANSWERS, 1, INPUT);
   }
 }
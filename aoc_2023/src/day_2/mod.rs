@@ -4,14 +4,7 @@
 
 use std::str::FromStr;
 
-use aoc::Solution;
-
-const INPUT: &str = include_str!("input.txt");
-
-#[derive(Debug)]
-enum ParseError {
-  InvalidInput,
-}
+use aoc::{Error, Result, Solution, Solver};
 
 #[derive(Default)]
 struct Conditions {
@@ -45,7 +38,7 @@ impl Set {
 }
 
 impl FromStr for Set {
-  type Err = ParseError;
+  type Err = Error;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
     let mut cubes: Vec<Cube> = Vec::new();
@@ -57,10 +50,10 @@ impl FromStr for Set {
         .collect::<Vec<_>>();
 
       let cube = match pairs.as_slice() {
-        | [n, "red"] => Cube::Red(n.parse::<u32>().unwrap_or_default()),
-        | [n, "green"] => Cube::Green(n.parse::<u32>().unwrap_or_default()),
-        | [n, "blue"] => Cube::Blue(n.parse::<u32>().unwrap_or_default()),
-        | _ => return Err(ParseError::InvalidInput),
+        | [n, "red"] => Cube::Red(n.parse::<u32>()?),
+        | [n, "green"] => Cube::Green(n.parse::<u32>()?),
+        | [n, "blue"] => Cube::Blue(n.parse::<u32>()?),
+        | _ => return Err(Error::parse(format!("invalid cubes `{value}`"))),
       };
 
       cubes.push(cube);
@@ -82,111 +75,106 @@ impl Game {
   }
 }
 
-fn solve_part_one(input: &str) -> u32 {
-  let conditions = Conditions {
-    reds: 12,
-    greens: 13,
-    blues: 14,
-  };
-
-  let mut result = 0;
-
-  for line in input.lines() {
-    let id = line
-      .chars()
-      .skip_while(|ch| !ch.is_ascii_digit()) // Skip prefix
-      .take_while(|ch| ch.is_ascii_digit()) // Consume id
-      .collect::<String>()
-      .parse::<u32>()
-      .unwrap_or_default();
-
-    let raw = line
-      .chars()
-      .skip_while(|ch| ch != &':')
-      .skip(1)
-      .collect::<String>();
-
-    let sets = raw
-      .trim()
-      .split(';')
-      .filter_map(|val| val.parse::<Set>().ok())
-      .collect::<Vec<_>>();
-
-    let game = Game { id, sets };
-
-    if game.satisfies(&conditions) {
-      result += game.id;
-    }
+fn parse_game(line: &str) -> Result<Game> {
+  let id = line
+    .chars()
+    .skip_while(|ch| !ch.is_ascii_digit()) // Skip prefix
+    .take_while(|ch| ch.is_ascii_digit()) // Consume id
+    .collect::<String>()
+    .parse::<u32>()
+    .map_err(|_| Error::parse(format!("no game id in `{line}`")))?;
+
+  let raw = line
+    .chars()
+    .skip_while(|ch| ch != &':')
+    .skip(1)
+    .collect::<String>();
+
+  let sets = raw
+    .trim()
+    .split(';')
+    .map(str::parse::<Set>)
+    .collect::<Result<Vec<_>>>()?;
+
+  Ok(Game { id, sets })
+}
+
+struct Day;
+
+impl Solver for Day {
+  type Parsed = Vec<Game>;
+  type PartOne = u32;
+  type PartTwo = u32;
+
+  const TITLE: &'static str = "Day 2: Cube Conundrum";
+
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    input.lines().map(parse_game).collect()
   }
 
-  result
-}
+  fn part_one(games: &Self::Parsed) -> Result<Self::PartOne> {
+    let conditions = Conditions {
+      reds: 12,
+      greens: 13,
+      blues: 14,
+    };
 
-fn solve_part_two(input: &str) -> u32 {
-  let mut result = 0;
-
-  for line in input.lines() {
-    let _ = line
-      .chars()
-      .skip_while(|ch| !ch.is_ascii_digit()) // Skip prefix
-      .take_while(|ch| ch.is_ascii_digit()) // Consume id
-      .collect::<String>()
-      .parse::<u32>()
-      .unwrap_or_default();
-
-    let raw = line
-      .chars()
-      .skip_while(|ch| ch != &':')
-      .skip(1)
-      .collect::<String>();
-
-    let cubes = raw
-      .trim()
-      .split(';')
-      .filter_map(|val| val.parse::<Set>().ok())
-      .flat_map(|set| set.cubes);
-
-    let mut mreds = 0;
-    let mut mgreens = 0;
-    let mut mblues = 0;
-
-    for cube in cubes {
-      match cube {
-        | Cube::Red(n) => mreds = mreds.max(n),
-        | Cube::Green(n) => mgreens = mgreens.max(n),
-        | Cube::Blue(n) => mblues = mblues.max(n),
+    let mut result = 0;
+
+    for game in games {
+      if game.satisfies(&conditions) {
+        result += game.id;
       }
     }
 
-    result += mreds * mgreens * mblues;
+    Ok(result)
   }
 
-  result
-}
+  fn part_two(games: &Self::Parsed) -> Result<Self::PartTwo> {
+    let mut result = 0;
 
-pub fn solution<'s>() -> Solution<'s, u32, u32> {
-  Solution {
-    title: "Day 2: Cube Conundrum",
-    part_one: solve_part_one(INPUT),
-    part_two: solve_part_two(INPUT),
+    for game in games {
+      let cubes = game.sets.iter().flat_map(|set| set.cubes.iter());
+
+      let mut mreds = 0;
+      let mut mgreens = 0;
+      let mut mblues = 0;
+
+      for cube in cubes {
+        match *cube {
+          | Cube::Red(n) => mreds = mreds.max(n),
+          | Cube::Green(n) => mgreens = mgreens.max(n),
+          | Cube::Blue(n) => mblues = mblues.max(n),
+        }
+      }
+
+      result += mreds * mgreens * mblues;
+    }
+
+    Ok(result)
   }
 }
 
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
   const EXAMPLE: &str = include_str!("example.txt");
 
   #[test]
   fn test_examples() {
-    assert_eq!(solve_part_one(EXAMPLE), 8);
-    assert_eq!(solve_part_two(EXAMPLE), 2286);
+    assert_eq!(Day::solve_part_one(EXAMPLE), Ok(8));
+    assert_eq!(Day::solve_part_two(EXAMPLE), Ok(2286));
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve_part_one(INPUT), 2810);
-    assert_eq!(solve_part_two(INPUT), 69110);
+    aoc::answers::assert::<Day>(ANSWERS, 2, INPUT);
   }
 }
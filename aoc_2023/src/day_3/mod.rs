@@ -1,230 +1,216 @@
 //! [Day 3: Gear Ratios][link]
 //!
-//! Overengineered and iefficient solution with nasty complexity, but it is what it is.
+//! Rows are tokenized first, so malformed numbers are caught, and then laid out on a grid where
+//! every digit knows which number it belongs to.
 //!
 //! [link]: https://adventofcode.com/2023/day/3
 
 use std::ops::Range;
 
-use aoc::Solution;
+use aoc::grid::{Grid, Position};
+use aoc::{Error, Mode, Result, Solution, Solver};
 use chumsky::prelude::*;
 
-const INPUT: &str = include_str!("input.txt");
-
-enum Check {
-  Inclusive,
-  Exclusive,
-}
-
 #[derive(Clone)]
 enum Token {
   Number(u32, Range<usize>),
-  Dot(Range<usize>),
+  Dot,
   Symbol(char, Range<usize>),
 }
 
-impl Token {
-  fn is_number(&self) -> bool {
-    matches!(self, Token::Number(..))
-  }
-
-  fn is_symbol(&self) -> bool {
-    matches!(self, Token::Symbol(..))
-  }
-
-  fn span(&self) -> &Range<usize> {
-    match self {
-      | Token::Dot(span) | Token::Number(_, span) | Token::Symbol(_, span) => span,
-    }
-  }
-
-  fn with_span(&self, span: Range<usize>) -> Self {
-    match self {
-      | Token::Number(value, ..) => Token::Number(*value, span),
-      | Token::Dot(..) => Token::Dot(span),
-      | Token::Symbol(kind, ..) => Token::Symbol(*kind, span),
-    }
-  }
-
-  fn overlaps_with(&self, tokens: &[Token], check: Check) -> bool {
-    let span = self.span();
-
-    tokens.iter().any(|token| {
-      if token.is_symbol() {
-        let other = token.span();
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Cell {
+  /// Digit of the number with the given index in [Schematic::numbers].
+  Digit(usize),
+  Dot,
+  Symbol(char),
+}
 
-        match check {
-          | Check::Inclusive => span.start < other.end && other.start < span.end,
-          | Check::Exclusive => span.start < other.end && other.start < span.end - 1,
-        }
-      } else {
-        false
-      }
-    })
-  }
+struct Schematic {
+  cells: Grid<Cell>,
+  numbers: Vec<u32>,
 }
 
-fn parse(line: &str) -> Vec<Token> {
-  let number = text::digits::<_, Simple<char>>(10)
-    .map_with_span(|digits, span| Token::Number(digits.parse().unwrap_or(0), span));
+fn parse(line: &str) -> Result<Vec<Token>> {
+  let number = text::digits::<_, Simple<char>>(10).try_map(|digits: String, span| {
+    digits
+      .parse()
+      .map(|value| Token::Number(value, span.clone()))
+      .map_err(|err| Simple::custom(span, format!("invalid number `{digits}`: {err}")))
+  });
 
-  let dot = just::<_, _, Simple<char>>('.').map_with_span(|_, span| Token::Dot(span));
+  let dot = just::<_, _, Simple<char>>('.').to(Token::Dot);
 
-  let symbol = choice::<_, Simple<char>>((dot, number))
-    .not()
-    .map_with_span(Token::Symbol);
+  // Anything else is a symbol. Digits are excluded explicitly, so a bad number fails the row
+  // instead of being read as a symbol.
+  let symbol = none_of::<_, _, Simple<char>>("0123456789.").map_with_span(Token::Symbol);
 
   let parser = choice::<_, Simple<char>>((number, dot, symbol))
     .repeated()
     .collect::<Vec<Token>>();
 
-  parser.parse(line).unwrap_or(vec![])
+  parser
+    .then_ignore(end())
+    .parse(line)
+    .map_err(|errs| Error::from_chumsky(line, errs))
 }
 
-#[inline]
-fn has_adjacents_at(board: &[Vec<Token>], row: usize, token: &Token) -> bool {
-  board.get(row).map_or(false, |tokens| {
-    token.overlaps_with(tokens, Check::Exclusive)
-  })
-}
+/// Tokenizes the schematic row by row and lays the tokens out on a grid. In lenient mode malformed
+/// rows are left blank, so the rows around them keep their positions.
+fn parse_schematic(input: &str, mode: Mode) -> Result<Schematic> {
+  let mut numbers = Vec::new();
+
+  let rows = input
+    .lines()
+    .enumerate()
+    .map(|(index, line)| {
+      let tokens = match parse(line) {
+        | Ok(tokens) => tokens,
+        | Err(_) if mode == Mode::Lenient => Vec::new(),
+        | Err(err) => return Err(err.on_line(index + 1)),
+      };
+
+      let mut row = vec![Cell::Dot; line.chars().count()];
+
+      for token in tokens {
+        match token {
+          | Token::Number(value, span) => {
+            row[span].fill(Cell::Digit(numbers.len()));
+            numbers.push(value);
+          },
+          | Token::Symbol(symbol, span) => row[span].fill(Cell::Symbol(symbol)),
+          | Token::Dot => {},
+        }
+      }
 
-fn has_adjacents(board: &[Vec<Token>], row: usize, token: &Token) -> bool {
-  let span = token.span();
+      Ok(row)
+    })
+    .collect::<Result<Vec<_>>>()?;
 
-  let col_min = span.start.saturating_sub(1);
-  let col_max = span.end.saturating_add(2);
+  Ok(Schematic {
+    cells: Grid::from_rows(rows)?,
+    numbers,
+  })
+}
 
-  let token = token.with_span(col_min..col_max);
+/// Indices of the numbers around the given cell, each listed once.
+fn adjacent_numbers(cells: &Grid<Cell>, position: Position) -> Vec<usize> {
+  let mut numbers = cells
+    .surrounding(position)
+    .filter_map(|position| {
+      match cells[position] {
+        | Cell::Digit(number) => Some(number),
+        | _ => None,
+      }
+    })
+    .collect::<Vec<_>>();
 
-  has_adjacents_at(board, row, &token)
-    || has_adjacents_at(board, row + 1, &token)
-    || has_adjacents_at(board, row.saturating_sub(1), &token)
+  numbers.sort_unstable();
+  numbers.dedup();
+  numbers
 }
 
-fn solve_part_one(input: &str) -> u32 {
-  let mut board: Vec<Vec<Token>> = Vec::new();
-  let mut result = 0;
-
-  for line in input.lines() {
-    board.push(parse(line));
-  }
+fn sum_part_numbers(schematic: &Schematic) -> u32 {
+  let mut parts = vec![false; schematic.numbers.len()];
 
-  for (row, line) in board.iter().enumerate() {
-    for token in line.iter() {
-      if let Token::Number(value, ..) = token {
-        if has_adjacents(&board, row, token) {
-          result += value;
-        }
+  for (position, cell) in schematic.cells.iter() {
+    if let Cell::Symbol(..) = cell {
+      for number in adjacent_numbers(&schematic.cells, position) {
+        parts[number] = true;
       }
     }
   }
 
-  result
+  schematic
+    .numbers
+    .iter()
+    .zip(parts)
+    .filter_map(|(value, part)| part.then_some(value))
+    .sum()
 }
 
-fn find_gear_parts(board: &[Vec<Token>], row: usize, token: &Token) -> Option<(Token, Token)> {
-  let span = token.span();
-
-  // So we stumbled a star symbol and should find exactly 2 numbers adjacent to it. They can be
-  // either in the current row or in the row above, or in the row below, so have to check them
-  // one-by-one and stop when we have exactly 2 numbers found.
-
-  // First, widen the search span so we can find numbers that lie diagonally.
-  let col_min = span.start.saturating_sub(1);
-  let col_max = span.end.saturating_add(1);
-
-  let star = token.with_span(col_min..col_max);
-
-  let mut first: Option<Token> = None;
-  let mut second: Option<Token> = None;
-
-  // Committing all possible crimes here, but whatever. Getting all possible tokens around the star.
-  let grid = {
-    let mut grid = Vec::new();
-
-    if let Some(row) = board.get(row.saturating_sub(1)) {
-      grid.extend(row.iter());
-    }
-
-    if let Some(row) = board.get(row) {
-      grid.extend(row.iter());
-    }
+fn sum_gear_ratios(schematic: &Schematic) -> u32 {
+  schematic
+    .cells
+    .iter()
+    .filter(|(_, cell)| **cell == Cell::Symbol('*'))
+    .filter_map(|(position, _)| {
+      match adjacent_numbers(&schematic.cells, position)[..] {
+        | [first, second] => Some(schematic.numbers[first] * schematic.numbers[second]),
+        | _ => None,
+      }
+    })
+    .sum()
+}
 
-    if let Some(row) = board.get(row + 1) {
-      grid.extend(row.iter());
-    }
+struct Day;
 
-    grid
-  };
+impl Solver for Day {
+  /// Engine schematic laid out on a grid.
+  type Parsed = Schematic;
+  type PartOne = u32;
+  type PartTwo = u32;
 
-  for token in grid.into_iter().filter(|token| token.is_number()) {
-    if first.is_some() && second.is_some() {
-      break;
-    }
+  const TITLE: &'static str = "Day 3: Gear Ratios";
 
-    if token.overlaps_with(&[star.clone()], Check::Inclusive) {
-      if first.is_none() {
-        first = Some(token.clone());
-      } else if second.is_none() {
-        second = Some(token.clone());
-      }
-    }
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    parse_schematic(input, Mode::Strict)
   }
 
-  first.zip(second)
-}
-
-fn solve_part_two(input: &str) -> u32 {
-  let mut board: Vec<Vec<Token>> = Vec::new();
-  let mut vals: Vec<(Token, Token)> = Vec::new();
-  let mut result = 0;
-
-  for line in input.lines() {
-    board.push(parse(line));
+  fn parse_lenient(input: &str) -> Result<Self::Parsed> {
+    parse_schematic(input, Mode::Lenient)
   }
 
-  for (row, line) in board.iter().enumerate() {
-    for token in line.iter() {
-      if let Token::Symbol('*', ..) = token {
-        if let Some(tokens) = find_gear_parts(&board, row, token) {
-          vals.push(tokens);
-        }
-      }
-    }
+  fn part_one(schematic: &Self::Parsed) -> Result<Self::PartOne> {
+    Ok(sum_part_numbers(schematic))
   }
 
-  for pair in vals.iter() {
-    if let (Token::Number(a, ..), Token::Number(b, ..)) = pair {
-      result += a * b;
-    }
+  fn part_two(schematic: &Self::Parsed) -> Result<Self::PartTwo> {
+    Ok(sum_gear_ratios(schematic))
   }
-
-  result
 }
 
-pub fn solution<'s>() -> Solution<'s, u32, u32> {
-  Solution {
-    title: "Day 3: Gear Ratios",
-    part_one: solve_part_one(INPUT),
-    part_two: solve_part_two(INPUT),
-  }
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
   const EXAMPLE: &str = include_str!("example.txt");
 
   #[test]
   fn test_examples() {
-    assert_eq!(solve_part_one(EXAMPLE), 4361);
-    assert_eq!(solve_part_two(EXAMPLE), 467835);
+    assert_eq!(Day::solve_part_one(EXAMPLE), Ok(4361));
+    assert_eq!(Day::solve_part_two(EXAMPLE), Ok(467835));
+  }
+
+  #[test]
+  fn test_malformed() {
+    let input = "467..114.................\n...*.....................\n..35555555555555555..633.\n";
+    let err = Day::parse(input).map(|_| ()).unwrap_err();
+
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 3, column 3: invalid number `35555555555555555`: number too large to \
+       fit in target type"
+    );
+    assert_eq!(
+      err.snippet().map(ToString::to_string).as_deref(),
+      Some("  |\n3 | ..35555555555555555..633.\n  |   ^^^^^^^^^^^^^^^^^")
+    );
+
+    assert_eq!(
+      Day::parse_lenient(input).and_then(|schematic| Day::part_one(&schematic)),
+      Ok(467)
+    );
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve_part_one(INPUT), 539590);
-    assert_eq!(solve_part_two(INPUT), 80703636);
+    aoc::answers::assert::<Day>(ANSWERS, 3, INPUT);
   }
 }
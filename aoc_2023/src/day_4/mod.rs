@@ -5,10 +5,7 @@
 use std::cmp::min;
 use std::collections::HashSet;
 
-use aoc::Solution;
-use chumsky::{prelude::*, text::whitespace};
-
-const INPUT: &str = include_str!("input.txt");
+use aoc::{parse, Error, Mode, Result, Solution, Solver};
 
 #[derive(Debug)]
 struct Card {
@@ -22,83 +19,137 @@ impl Card {
   }
 }
 
-fn parse(input: &str) -> Option<Card> {
-  let prefix = any::<_, Simple<char>>()
-    .then(take_until(just(':')))
-    .padded();
-
-  let numbers = text::digits::<_, Simple<char>>(10)
-    .map(|digits| digits.parse::<u32>().unwrap_or(0))
-    .separated_by(whitespace())
-    .padded();
-
-  let parser = prefix
-    .ignore_then(numbers)
-    .then(just('|').padded().ignored())
-    .then(numbers)
-    .map(|((winning, _), numbers)| {
-      Card {
-        winning: HashSet::from_iter(winning),
-        numbers: HashSet::from_iter(numbers),
-      }
-    });
+fn parse_card(line: &str) -> Result<Card> {
+  let Some((_, card)) = parse::key_value(line) else {
+    let expected = ["`Card <id>: <numbers> | <numbers>`"];
+    return Err(Error::at(line, line.trim(), "invalid card", expected));
+  };
+
+  let numbers = |text: &str| {
+    parse::list::<u32>(text, ' ')
+      .map(HashSet::from_iter)
+      .map_err(|err| err.within(line, text))
+  };
+
+  let (winning, rest) = card.split_once('|').unwrap_or((card, ""));
+  let winning = numbers(winning)?;
 
-  parser.parse(input).ok()
+  if !card.contains('|') {
+    let end = &line[line.len()..];
+    return Err(Error::at(line, end, "unexpected end of line", ["`|`"]));
+  }
+
+  Ok(Card {
+    winning,
+    numbers: numbers(rest)?,
+  })
 }
 
-fn solve_part_one(input: &str) -> u32 {
-  let mut result = 0;
+/// Parses a card per line. In lenient mode malformed cards are skipped.
+fn parse_cards(input: &str, mode: Mode) -> Result<Vec<Card>> {
+  input
+    .lines()
+    .enumerate()
+    .filter_map(|(index, line)| {
+      match parse_card(line) {
+        | Ok(card) => Some(Ok(card)),
+        | Err(_) if mode == Mode::Lenient => None,
+        | Err(err) => Some(Err(err.on_line(index + 1))),
+      }
+    })
+    .collect()
+}
 
-  for card in input.lines().filter_map(parse) {
-    let points = card.points();
+struct Day;
 
-    if points > 0 {
-      result += 2u32.pow(points - 1);
-    }
+impl Solver for Day {
+  type Parsed = Vec<Card>;
+  type PartOne = u32;
+  type PartTwo = u32;
+
+  const TITLE: &'static str = "Day 4: Scratchcards";
+
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    parse_cards(input, Mode::Strict)
   }
 
-  result
-}
+  fn parse_lenient(input: &str) -> Result<Self::Parsed> {
+    parse_cards(input, Mode::Lenient)
+  }
 
-fn solve_part_two(input: &str) -> u32 {
-  let cards: Vec<Card> = input.lines().filter_map(parse).collect();
-  let mut totals = vec![1; cards.len()];
+  fn part_one(cards: &Self::Parsed) -> Result<Self::PartOne> {
+    let mut result = 0;
 
-  for idx in 1..cards.len() {
-    let card = &cards[idx - 1];
-    let points = card.points();
+    for card in cards {
+      let points = card.points();
 
-    for x in idx..min(totals.len(), idx + (points as usize)) {
-      totals[x] += totals[idx - 1];
+      if points > 0 {
+        result += 2u32.pow(points - 1);
+      }
     }
+
+    Ok(result)
   }
 
-  totals.iter().sum()
-}
+  fn part_two(cards: &Self::Parsed) -> Result<Self::PartTwo> {
+    let mut totals = vec![1; cards.len()];
+
+    for idx in 1..cards.len() {
+      let card = &cards[idx - 1];
+      let points = card.points();
+
+      for x in idx..min(totals.len(), idx + (points as usize)) {
+        totals[x] += totals[idx - 1];
+      }
+    }
 
-pub fn solution<'s>() -> Solution<'s, u32, u32> {
-  Solution {
-    title: "Day 4: Scratchcards",
-    part_one: solve_part_one(INPUT),
-    part_two: solve_part_two(INPUT),
+    Ok(totals.iter().sum())
   }
 }
 
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
   const EXAMPLE: &str = include_str!("example.txt");
 
   #[test]
   fn test_examples() {
-    assert_eq!(solve_part_one(EXAMPLE), 13);
-    assert_eq!(solve_part_two(EXAMPLE), 30);
+    assert_eq!(Day::solve_part_one(EXAMPLE), Ok(13));
+    assert_eq!(Day::solve_part_two(EXAMPLE), Ok(30));
+  }
+
+  #[test]
+  fn test_malformed() {
+    let input = "Card 1: 41 48 | 83 86\nCard 2: 13 32 - 61 30\nCard 3: 1 21 | 69 82\n";
+    let err = Day::parse(input).map(|_| ()).unwrap_err();
+
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 2, column 15: invalid value `-`: invalid digit found in string"
+    );
+    assert_eq!(
+      err.snippet().map(ToString::to_string).as_deref(),
+      Some("  |\n2 | Card 2: 13 32 - 61 30\n  |               ^")
+    );
+
+    let err = Day::parse("Card 1: 41 48 83 86\n").map(|_| ()).unwrap_err();
+    assert_eq!(
+      err.snippet().map(ToString::to_string).as_deref(),
+      Some("  |\n1 | Card 1: 41 48 83 86\n  |                    ^ expected `|`")
+    );
+
+    assert_eq!(Day::parse_lenient(input).map(|cards| cards.len()), Ok(2));
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve_part_one(INPUT), 32001);
-    assert_eq!(solve_part_two(INPUT), 5037841);
+    aoc::answers::assert::<Day>(ANSWERS, 4, INPUT);
   }
 }
@@ -1,284 +1,236 @@
 //! [Day 5: If You Give A Seed A Fertilizer][link]
 //!
+//! Every map of the almanac is a piecewise-linear function, so they compose into a single one
+//! mapping seeds straight to locations. The almanac displays as the table of that map, e.g. via
+//! `println!("{almanac}")`.
+//!
 //! [link]: https://adventofcode.com/2023/day/5
 
+use std::fmt::{self, Display};
 use std::str::FromStr;
 
-use aoc::Solution;
-use chumsky::prelude::*;
-use chumsky::text::*;
-
-const INPUT: &str = include_str!("input.txt");
+use aoc::interval::{Interval, IntervalSet, Piecewise};
+use aoc::{parse, Error, Result, Solution, Solver};
 
 #[derive(Debug)]
-enum ParseError {
-  InvalidInput,
-}
-
-struct SeedResult {
-  tx: Option<Seed>,
-  lo: Option<Seed>,
-  hi: Option<Seed>,
+struct Almanac {
+  seeds: Vec<i64>,
+  maps: Vec<Piecewise>,
 }
 
-impl SeedResult {
-  fn new() -> Self {
-    Self {
-      tx: None,
-      lo: None,
-      hi: None,
+impl Almanac {
+  /// Seed ranges, read from the list of seeds as pairs of start and length.
+  fn seed_ranges(&self) -> Result<IntervalSet> {
+    if !self.seeds.len().is_multiple_of(2) {
+      return Err(Error::no_solution(format!(
+        "odd number of seeds: {}, expected pairs of seed starts and lengths",
+        self.seeds.len()
+      )));
     }
-  }
-}
-
-#[derive(Debug)]
-struct Seed {
-  lo: isize,
-  hi: isize,
-}
 
-impl Seed {
-  fn split(&self, index: isize) -> (Seed, Seed) {
-    (
-      Seed {
-        lo: self.lo,
-        hi: index,
-      },
-      Seed {
-        lo: index,
-        hi: self.hi,
-      },
+    Ok(
+      self
+        .seeds
+        .chunks_exact(2)
+        .map(|pair| Interval::with_len(pair[0], pair[1]))
+        .collect(),
     )
   }
-}
 
-#[derive(Debug)]
-struct Mapping {
-  lo: isize,
-  hi: isize,
-  tx: isize,
+  /// All the maps composed into a single seed-to-location map.
+  fn composed(&self) -> Piecewise {
+    self
+      .maps
+      .iter()
+      .fold(Piecewise::new(), |composed, map| composed.then(map))
+  }
 }
 
-impl Mapping {
-  fn tx(&self, x: isize) -> Option<isize> {
-    if x >= self.lo && x < self.hi {
-      Some(x + self.tx)
-    } else {
-      None
-    }
+/// Table of the composed seed-to-location map.
+impl Display for Almanac {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.composed())
   }
+}
 
-  fn txs(&self, seed: Seed) -> SeedResult {
-    let mut seed = seed;
-    let mut out = SeedResult::new();
-
-    if seed.hi <= self.lo {
-      out.lo = Some(seed);
-      return out;
-    }
+impl FromStr for Almanac {
+  type Err = Error;
 
-    if seed.lo >= self.hi {
-      out.hi = Some(seed);
-      return out;
-    }
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut paragraphs = parse::paragraphs(s);
+
+    let seeds = paragraphs.next().unwrap_or_default();
+    let values = parse::field(seeds, "seeds").map_err(|err| err.within(s, seeds))?;
+    let seeds = parse::list::<i64>(values, ' ').map_err(|err| err.within(s, values))?;
+
+    let range = |line: &str| {
+      match parse::list::<i64>(line, ' ').map_err(|err| err.within(s, line))?[..] {
+        | [dest, source, len] => Ok((Interval::with_len(source, len), dest - source)),
+        | _ => {
+          let expected = ["`<destination> <source> <length>`"];
+          Err(Error::at(s, line, "invalid range", expected))
+        },
+      }
+    };
 
-    if seed.lo < self.lo && self.lo < seed.hi {
-      let (before, after) = seed.split(self.lo);
-      out.lo = Some(before);
-      seed = after;
-    }
+    // Every map is a header line followed by its ranges.
+    let maps = paragraphs
+      .map(|map| {
+        let mut lines = map.lines();
+        let header = lines.next().unwrap_or_default();
 
-    if seed.hi > self.hi && self.hi > seed.lo {
-      let (before, after) = seed.split(self.hi);
-      out.hi = Some(after);
-      seed = before;
-    }
+        if !header.ends_with("map:") {
+          let expected = ["`<source>-to-<destination> map:`"];
+          return Err(Error::at(s, header, "invalid map header", expected));
+        }
 
-    seed.lo += self.tx;
-    seed.hi += self.tx;
-    out.tx = Some(seed);
+        lines.map(range).collect::<Result<Piecewise>>()
+      })
+      .collect::<Result<Vec<_>>>()?;
 
-    out
+    Ok(Almanac { seeds, maps })
   }
 }
 
-#[derive(Debug)]
-struct Map {
-  mappings: Vec<Mapping>,
-}
-
-impl Map {
-  fn tx(&self, x: isize) -> isize {
-    self
-      .mappings
-      .iter()
-      .map(|mapping| mapping.tx(x))
-      .try_fold(x, |acc, y| y.or(Some(acc)))
-      .unwrap_or(0)
-  }
+struct Day;
 
-  fn txs(&self, seeds: Vec<Seed>) -> Vec<Seed> {
-    let mut seeds = seeds;
-    let mut mapped = Vec::new();
+impl Solver for Day {
+  type Parsed = Almanac;
+  type PartOne = u32;
+  type PartTwo = u32;
 
-    for map in self.mappings.iter() {
-      let mut outside = Vec::new();
+  const TITLE: &'static str = "Day 5: If You Give A Seed A Fertilizer";
 
-      for seed in seeds.into_iter() {
-        let res = map.txs(seed);
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    Almanac::from_str(input)
+  }
 
-        if let Some(s) = res.lo {
-          outside.push(s)
-        };
+  fn part_one(almanac: &Self::Parsed) -> Result<Self::PartOne> {
+    let composed = almanac.composed();
 
-        if let Some(s) = res.tx {
-          mapped.push(s)
-        };
+    let location = almanac
+      .seeds
+      .iter()
+      .map(|&seed| composed.apply(seed))
+      .min()
+      .ok_or_else(|| Error::no_solution("there are no seeds"))?;
 
-        if let Some(s) = res.hi {
-          outside.push(s)
-        };
-      }
+    Ok(location.try_into()?)
+  }
 
-      seeds = outside;
-    }
+  fn part_two(almanac: &Self::Parsed) -> Result<Self::PartTwo> {
+    let location = almanac
+      .composed()
+      .apply_set(&almanac.seed_ranges()?)
+      .min()
+      .ok_or_else(|| Error::no_solution("there are no seeds"))?;
 
-    mapped.append(&mut seeds);
-    mapped
+    Ok(location.try_into()?)
   }
 }
 
-#[derive(Debug, Default)]
-struct Almanac {
-  seeds: Vec<isize>,
-  maps: Vec<Map>,
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
 }
 
-impl FromStr for Almanac {
-  type Err = ParseError;
+#[cfg(test)]
+mod tests {
+  use super::*;
 
-  fn from_str(s: &str) -> Result<Self, Self::Err> {
-    let prefix = just::<_, _, Simple<char>>(':')
-      .not()
-      .repeated()
-      .then_ignore(just(':'));
-
-    let number = text::int::<_, Simple<char>>(10);
-
-    let seeds = prefix
-      .padded()
-      .ignore_then(number.separated_by(whitespace()))
-      .map(|vals| {
-        vals
-          .into_iter()
-          .filter_map(|val| val.parse::<isize>().ok())
-          .collect::<Vec<_>>()
-      });
-
-    let range = number.separated_by(whitespace()).exactly(3).map(|vals| {
-      vals
-        .into_iter()
-        .filter_map(|val| val.parse::<isize>().ok())
-        .collect::<Vec<_>>()
-    });
-
-    let map = prefix
-      .ignore_then(newline())
-      .ignore_then(range.separated_by(newline()))
-      .map(|ranges| {
-        let mappings = ranges
-          .into_iter()
-          .filter_map(|range| {
-            match range.as_slice() {
-              | &[dest, source, len] => {
-                Some(Mapping {
-                  lo: source,
-                  hi: source + len,
-                  tx: dest - source,
-                })
-              },
-              // This actually means that the input is broken.
-              | _ => None,
-            }
-          })
-          .collect();
-
-        Map { mappings }
-      });
-
-    let maps = map.separated_by(newline());
-
-    let almanac = seeds
-      .then_ignore(newline())
-      .then(maps)
-      .map(|(seeds, maps)| Almanac { seeds, maps });
-
-    almanac.parse(s).map_err(|_| ParseError::InvalidInput)
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
+  const EXAMPLE: &str = include_str!("example.txt");
+
+  #[test]
+  fn test_examples() {
+    assert_eq!(Day::solve_part_one(EXAMPLE), Ok(35));
+    assert_eq!(Day::solve_part_two(EXAMPLE), Ok(46));
   }
-}
 
-fn solve_part_one(input: &str) -> u32 {
-  let almanac = Almanac::from_str(input).unwrap_or_default();
+  #[test]
+  fn test_composed() {
+    for input in [EXAMPLE, INPUT] {
+      let almanac = Day::parse(input).unwrap();
+      let composed = almanac.composed();
+      let seeds = almanac.seed_ranges().unwrap();
+
+      // Pushing seeds through the maps one by one lands them on the same locations.
+      let staged = almanac
+        .maps
+        .iter()
+        .fold(seeds.clone(), |acc, map| map.apply_set(&acc));
+
+      assert_eq!(composed.apply_set(&seeds), staged);
+
+      for &seed in &almanac.seeds {
+        let location = almanac.maps.iter().fold(seed, |acc, map| map.apply(acc));
+        assert_eq!(composed.apply(seed), location);
+      }
 
-  let location = almanac
-    .seeds
-    .iter()
-    .map(|seed| almanac.maps.iter().fold(*seed, |acc, map| map.tx(acc)))
-    .min()
-    .unwrap_or(0);
+      // No seed lands below the lowest location, and some land right on it.
+      let lowest = i64::from(Day::part_two(&almanac).unwrap());
+      let below = composed.preimage(&Interval::new(0, lowest).into());
+      let landing = composed.preimage(&Interval::with_len(lowest, 1).into());
 
-  location as u32
-}
+      assert!(below.intersection(&seeds).is_empty());
+      assert!(!landing.intersection(&seeds).is_empty());
+    }
+  }
 
-fn solve_part_two(input: &str) -> u32 {
-  let almanac = Almanac::from_str(input).unwrap_or_default();
-
-  let seeds = almanac
-    .seeds
-    .chunks(2)
-    .map(|chunk| {
-      let lo = chunk[0];
-      let hi = chunk[0] + chunk[1];
-
-      Seed { lo, hi }
-    })
-    .collect::<Vec<_>>();
-
-  let location = almanac
-    .maps
-    .iter()
-    .fold(seeds, |acc, map| map.txs(acc))
-    .iter()
-    .map(|x| x.lo)
-    .min()
-    .unwrap_or(0);
-
-  location as u32
-}
+  #[test]
+  fn test_display() {
+    let almanac = Day::parse(EXAMPLE).unwrap();
+    let table = almanac.to_string();
 
-pub fn solution<'s>() -> Solution<'s, u32, u32> {
-  Solution {
-    title: "Day 5: If You Give A Seed A Fertilizer",
-    part_one: solve_part_one(INPUT),
-    part_two: solve_part_two(INPUT),
+    assert_eq!(table, almanac.composed().to_string());
+    assert_eq!(table.lines().next(), Some("[0, 14) -> [22, 36) (+22)"));
   }
-}
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+  #[test]
+  fn test_malformed() {
+    let input = EXAMPLE.replacen("52 50 48", "52 50 4x", 1);
+    let err = Day::parse(&input).map(|_| ()).unwrap_err();
+
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 5, column 7: invalid value `4x`: invalid digit found in string"
+    );
+    assert_eq!(
+      err.snippet().map(ToString::to_string).as_deref(),
+      Some("  |\n5 | 52 50 4x\n  |       ^^")
+    );
+  }
 
-  const EXAMPLE: &str = include_str!("example.txt");
+  #[test]
+  fn test_malformed_header() {
+    let input = EXAMPLE.replacen("soil-to-fertilizer map:", "soil-to-fertilizer", 1);
+    let err = Day::parse(&input).map(|_| ()).unwrap_err();
+
+    assert_eq!(
+      err.to_string(),
+      "invalid input at line 7, column 1: invalid map header"
+    );
+    assert_eq!(
+      err.snippet().map(ToString::to_string).as_deref(),
+      Some(
+        "  |\n7 | soil-to-fertilizer\n  | ^^^^^^^^^^^^^^^^^^ expected `<source>-to-<destination> map:`"
+      )
+    );
+  }
 
   #[test]
-  fn test_examples() {
-    assert_eq!(solve_part_one(EXAMPLE), 35);
-    assert_eq!(solve_part_two(EXAMPLE), 46);
+  fn test_odd_seeds() {
+    let input = EXAMPLE.replacen("seeds: 79 14 55 13", "seeds: 79 14 55", 1);
+
+    assert_eq!(Day::solve_part_one(&input), Ok(43));
+    assert_eq!(
+      Day::solve_part_two(&input).unwrap_err().to_string(),
+      "no solution: odd number of seeds: 3, expected pairs of seed starts and lengths"
+    );
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve_part_one(INPUT), 227653707);
-    assert_eq!(solve_part_two(INPUT), 78775051);
+    aoc::answers::assert::<Day>(ANSWERS, 5, INPUT);
   }
 }
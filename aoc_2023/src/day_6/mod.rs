@@ -4,14 +4,7 @@
 
 use std::str::FromStr;
 
-use aoc::Solution;
-
-const INPUT: &str = include_str!("input.txt");
-
-#[derive(Debug)]
-enum ParseError {
-  InvalidInput,
-}
+use aoc::{parse, Error, Result, Solution, Solver};
 
 #[derive(Debug)]
 struct Race {
@@ -21,7 +14,7 @@ struct Race {
 
 impl Race {
   fn permutations(&self) -> usize {
-    let (is_even, max) = if self.time % 2 == 0 {
+    let (is_even, max) = if self.time.is_multiple_of(2) {
       (true, (self.time / 2).pow(2))
     } else {
       (false, self.time / 2 * (self.time / 2 + 1))
@@ -59,106 +52,108 @@ impl Document {
   }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 struct Table {
   times: Vec<usize>,
   records: Vec<usize>,
 }
 
 impl Table {
-  fn into_document(self) -> Document {
+  fn to_document(&self) -> Document {
     Document {
       races: self
         .times
-        .into_iter()
-        .zip(self.records)
-        .map(|(time, record)| Race { time, record })
+        .iter()
+        .zip(self.records.iter())
+        .map(|(&time, &record)| Race { time, record })
         .collect::<Vec<_>>(),
     }
   }
 
-  fn into_sr_document(self) -> Document {
-    fn fuse(vec: Vec<usize>) -> usize {
+  fn to_sr_document(&self) -> Document {
+    fn fuse(vec: &[usize]) -> usize {
       vec
-        .into_iter()
+        .iter()
         .fold(0, |acc, x| acc * 10usize.pow(x.ilog10() + 1) + x)
     }
 
     Document {
       races: vec![Race {
-        time: fuse(self.times),
-        record: fuse(self.records),
+        time: fuse(&self.times),
+        record: fuse(&self.records),
       }],
     }
   }
 }
 
 impl FromStr for Table {
-  type Err = ParseError;
+  type Err = Error;
 
   fn from_str(input: &str) -> Result<Self, Self::Err> {
-    fn parse(line: &str, prefix: &str) -> Vec<usize> {
-      line
-        .strip_prefix(prefix)
-        .map(str::trim)
-        .map(|line| {
-          line
-            .split_whitespace()
-            .filter_map(|s| s.parse::<usize>().ok())
-            .collect::<Vec<_>>()
-        })
-        .unwrap_or_default()
-    }
+    let mut lines = input.lines();
+
+    let mut values = |key: &str| {
+      let line = lines
+        .next()
+        .ok_or_else(|| Error::parse(format!("expected the `{key}` line")))?;
+      let values = parse::field(line, key).map_err(|err| err.within(input, line))?;
+
+      parse::list(values, ' ').map_err(|err| err.within(input, values))
+    };
 
-    input
-      .split_once('\n')
-      .map(|(times, distances)| {
-        let times = parse(times, "Time:");
-        let records = parse(distances, "Distance:");
+    let times = values("Time")?;
+    let records = values("Distance")?;
 
-        Table { times, records }
-      })
-      .ok_or(ParseError::InvalidInput)
+    Ok(Table { times, records })
   }
 }
 
-fn solve_part_one(input: &str) -> u32 {
-  let table = Table::from_str(input).unwrap_or_default();
-  let total = table.into_document().total_permutations();
+struct Day;
 
-  total as u32
-}
+impl Solver for Day {
+  type Parsed = Table;
+  type PartOne = u32;
+  type PartTwo = u32;
 
-fn solve_part_two(input: &str) -> u32 {
-  let table = Table::from_str(input).unwrap_or_default();
-  let total = table.into_sr_document().total_permutations();
+  const TITLE: &'static str = "Day 6: Wait For It";
 
-  total as u32
-}
+  fn parse(input: &str) -> Result<Self::Parsed> {
+    Table::from_str(input)
+  }
 
-pub fn solution<'s>() -> Solution<'s, u32, u32> {
-  Solution {
-    title: "Day 6: Wait For It",
-    part_one: solve_part_one(INPUT),
-    part_two: solve_part_two(INPUT),
+  fn part_one(table: &Self::Parsed) -> Result<Self::PartOne> {
+    let total = table.to_document().total_permutations();
+
+    Ok(total.try_into()?)
   }
+
+  fn part_two(table: &Self::Parsed) -> Result<Self::PartTwo> {
+    let total = table.to_sr_document().total_permutations();
+
+    Ok(total.try_into()?)
+  }
+}
+
+pub fn solution() -> Solution {
+  Solution::new::<Day>()
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const INPUT: &str = include_str!("input.txt");
+  const ANSWERS: &str = include_str!("../../answers.toml");
   const EXAMPLE: &str = include_str!("example.txt");
 
   #[test]
   fn test_examples() {
-    assert_eq!(solve_part_one(EXAMPLE), 288);
-    assert_eq!(solve_part_two(EXAMPLE), 71503);
+    assert_eq!(Day::solve_part_one(EXAMPLE), Ok(288));
+    assert_eq!(Day::solve_part_two(EXAMPLE), Ok(71503));
   }
 
   #[test]
   fn test_input() {
-    assert_eq!(solve_part_one(INPUT), 227850);
-    assert_eq!(solve_part_two(INPUT), 42948149);
+    aoc::answers::assert::<Day>(ANSWERS, 6, INPUT);
   }
 }
@@ -0,0 +1,3 @@
+//! Day modules are registered automatically: `build.rs` picks up every `src/day_N` directory.
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
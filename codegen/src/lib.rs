@@ -0,0 +1,151 @@
+//! Code generation for build scripts of the workspace crates. Generated sources are written to
+//! `OUT_DIR` and pulled in by the crate roots with `include!`.
+
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+fn env_var(name: &str) -> String {
+  env::var(name).unwrap_or_else(|_| panic!("{name} should be set by cargo"))
+}
+
+fn write(file: &str, source: String) {
+  let out = PathBuf::from(env_var("OUT_DIR"));
+
+  fs::write(out.join(file), source).unwrap_or_else(|err| panic!("failed to write {file}: {err}"));
+}
+
+/// Parses a `{prefix}{number}` name, e.g. `day_12` or `aoc_2022`.
+fn numbered<T: std::str::FromStr>(name: &str, prefix: &str) -> Option<T> {
+  name
+    .strip_prefix(prefix)
+    .filter(|number| !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+    .and_then(|number| number.parse().ok())
+}
+
+/// Finds `src/day_N/mod.rs` modules, ordered by day number.
+fn find_days(src: &Path) -> Vec<(u8, PathBuf)> {
+  let entries = fs::read_dir(src).unwrap_or_else(|err| panic!("failed to read {src:?}: {err}"));
+
+  let mut days = entries
+    .filter_map(Result::ok)
+    .filter_map(|entry| {
+      let day = numbered::<u8>(entry.file_name().to_str()?, "day_")?;
+      let module = entry.path().join("mod.rs");
+
+      module.is_file().then_some((day, module))
+    })
+    .collect::<Vec<_>>();
+
+  days.sort_by_key(|(day, _)| *day);
+  days
+}
+
+/// Registers every `src/day_N` module of an `aoc_YYYY` crate. Generates `days.rs` with the module
+/// declarations and a `year()` function returning the puzzles ordered by day number, each with
+/// the `input.txt` next to its module, and the `answers.toml` manifest of the crate if it exists.
+///
+/// Meant to be called from `build.rs` of a year crate.
+pub fn days() {
+  let name = env_var("CARGO_PKG_NAME");
+  let year = numbered::<u16>(&name, "aoc_")
+    .unwrap_or_else(|| panic!("expected a crate named `aoc_YYYY`, got `{name}`"));
+
+  let root = PathBuf::from(env_var("CARGO_MANIFEST_DIR"));
+  let days = find_days(&root.join("src"));
+
+  let modules = days
+    .iter()
+    .map(|(day, module)| format!("#[path = {module:?}]\nmod day_{day};\n"))
+    .collect::<String>();
+
+  let puzzles = days
+    .iter()
+    .map(|(day, module)| {
+      let input = module.with_file_name("input.txt");
+
+      format!("      aoc::Puzzle::new({day}, day_{day}::solution(), aoc::bundled!({input:?})),\n")
+    })
+    .collect::<String>();
+
+  let manifest = root.join("answers.toml");
+
+  let answers = if manifest.is_file() {
+    format!("\n  .with_answers(aoc::bundled!({manifest:?}))")
+  } else {
+    String::new()
+  };
+
+  let source = format!(
+    "{modules}\npub fn year() -> aoc::Year {{\n  aoc::Year::new(\n    {year},\n    vec![\n{puzzles}    ],\n  ){answers}\n}}\n"
+  );
+
+  write("days.rs", source);
+
+  println!("cargo:rerun-if-changed=src");
+  println!("cargo:rerun-if-changed=answers.toml");
+}
+
+/// Names of the `aoc_YYYY` crates among the `[dependencies]` of a manifest, ordered by year.
+fn year_crates(manifest: &str) -> Vec<String> {
+  let manifest = manifest
+    .parse::<toml::Table>()
+    .unwrap_or_else(|err| panic!("failed to parse Cargo.toml: {err}"));
+
+  let mut crates = manifest
+    .get("dependencies")
+    .and_then(toml::Value::as_table)
+    .into_iter()
+    .flat_map(|dependencies| dependencies.keys())
+    .filter_map(|name| Some((numbered::<u16>(name, "aoc_")?, name.clone())))
+    .collect::<Vec<_>>();
+
+  crates.sort();
+  crates.into_iter().map(|(_, name)| name).collect()
+}
+
+/// Registers every `aoc_YYYY` crate listed in the `[dependencies]` of `Cargo.toml`, ordered by
+/// year. Generates `years.rs` with a `years()` function returning all of them.
+///
+/// Meant to be called from `build.rs` of the runner.
+pub fn years() {
+  let manifest = fs::read_to_string("Cargo.toml").expect("failed to read Cargo.toml");
+
+  let years = year_crates(&manifest)
+    .into_iter()
+    .map(|name| format!("    {name}::year(),\n"))
+    .collect::<String>();
+
+  write(
+    "years.rs",
+    format!("pub fn years() -> Vec<aoc::Year> {{\n  vec![\n{years}  ]\n}}\n"),
+  );
+
+  println!("cargo:rerun-if-changed=Cargo.toml");
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_year_crates() {
+    let manifest = r#"
+      # aoc_2019 = { path = "../aoc_2019" }
+
+      [dependencies]
+      aoc = { workspace = true }
+      aoc_2023 = { path = "../aoc_2023" }
+
+      [dependencies.aoc_2022]
+      path = "../aoc_2022"
+
+      [dev-dependencies]
+      aoc_2021 = { path = "../aoc_2021" }
+
+      [features]
+      aoc_2020 = []
+    "#;
+
+    assert_eq!(year_crates(manifest), ["aoc_2022", "aoc_2023"]);
+  }
+}
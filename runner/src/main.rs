@@ -0,0 +1,9 @@
+use std::process::ExitCode;
+
+mod registry {
+  include!(concat!(env!("OUT_DIR"), "/years.rs"));
+}
+
+fn main() -> ExitCode {
+  aoc::cli::run_workspace(registry::years())
+}
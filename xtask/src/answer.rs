@@ -0,0 +1,98 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use aoc::answers::Answers;
+use aoc::Part;
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::{Confirm, Input};
+
+const MANIFEST_HEADER: &str =
+  "# Answers to the bundled inputs, checked by the runner and the `test_input` tests.\n\n";
+
+#[derive(Debug)]
+struct Answer {
+  year: String,
+  day: u8,
+  part: Part,
+  answer: String,
+}
+
+impl Answer {
+  fn path(&self) -> PathBuf {
+    PathBuf::from(format!("aoc_{year}/answers.toml", year = self.year))
+  }
+
+  fn read(&self) -> io::Result<Answers> {
+    let manifest = match fs::read_to_string(self.path()) {
+      | Ok(manifest) => manifest,
+      | Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+      | Err(err) => return Err(err),
+    };
+
+    Answers::parse(&manifest).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+  }
+
+  fn write(&self, mut answers: Answers) -> io::Result<()> {
+    answers.insert(self.day, self.part, aoc::Answer::parse(&self.answer));
+
+    fs::write(self.path(), format!("{MANIFEST_HEADER}{answers}"))
+  }
+}
+
+pub fn run() {
+  let year: String = Input::with_theme(&ColorfulTheme::default())
+    .with_prompt("Year")
+    .interact_text()
+    .expect("failed to read year");
+
+  let day: u8 = Input::with_theme(&ColorfulTheme::default())
+    .with_prompt("Day")
+    .interact_text()
+    .expect("failed to read day");
+
+  let part: String = Input::with_theme(&ColorfulTheme::default())
+    .with_prompt("Part")
+    .validate_with(|part: &String| part.parse::<Part>().map(|_| ()))
+    .interact_text()
+    .expect("failed to read part");
+
+  let answer: String = Input::with_theme(&ColorfulTheme::default())
+    .with_prompt("Answer")
+    .interact_text()
+    .expect("failed to read answer");
+
+  let answer = Answer {
+    year,
+    day,
+    part: part.parse().expect("part should be validated"),
+    answer,
+  };
+
+  let result = answer.read().and_then(|answers| {
+    if let Some(recorded) = answers.get(answer.day, answer.part) {
+      let overwrite = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!("Replace the recorded answer `{recorded}`?"))
+        .interact()
+        .expect("failed to read confirmation");
+
+      if !overwrite {
+        return Ok(());
+      }
+    }
+
+    answer.write(answers)
+  });
+
+  match result {
+    | Ok(_) => {
+      println!("  ———");
+      println!("· Done!");
+    },
+    | Err(err) => {
+      eprintln!("  ———");
+      eprintln!("· Failed!");
+      eprintln!("    Details: {err:?}");
+    },
+  }
+}
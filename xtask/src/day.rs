@@ -6,47 +6,62 @@ use dialoguer::theme::ColorfulTheme;
 use dialoguer::Input;
 use indoc::indoc;
 
+const ANSWERS_TEMPLATE: &str = indoc! {"
+  # Answers to the bundled inputs, checked by the runner and the `test_input` tests.
+"};
+
 const MOD_TEMPLATE: &str = indoc! {r#"
   //! [Day {day}: {day_title}][link]
   //!
   //! [link]: https://adventofcode.com/{year}/day/{day}
 
-  use aoc::Solution;
+  use aoc::{Result, Solution, Solver};
 
-  const INPUT: &str = include_str!("input.txt");
+  struct Day;
 
-  fn solve_part_one(_input: &str) -> u32 {
-    0
-  }
+  impl Solver for Day {
+    type Parsed = ();
+    type PartOne = u32;
+    type PartTwo = u32;
 
-  fn solve_part_two(_input: &str) -> u32 {
-    0
-  }
+    const TITLE: &'static str = "Day {day}: {day_title}";
 
-  pub fn solution<'s>() -> Solution<'s, u32, u32> {
-    Solution {
-      title: "Day {day}: {day_title}",
-      part_one: solve_part_one(INPUT),
-      part_two: solve_part_two(INPUT),
+    fn parse(_input: &str) -> Result<Self::Parsed> {
+      Ok(())
     }
+
+    fn part_one(_parsed: &Self::Parsed) -> Result<Self::PartOne> {
+      Ok(0)
+    }
+
+    fn part_two(_parsed: &Self::Parsed) -> Result<Self::PartTwo> {
+      Ok(0)
+    }
+  }
+
+  pub fn solution() -> Solution {
+    Solution::new::<Day>()
   }
 
   #[cfg(test)]
   mod tests {
     use super::*;
 
+    const INPUT: &str = include_str!("input.txt");
+    const ANSWERS: &str = include_str!("../../answers.toml");
     const EXAMPLE: &str = include_str!("example.txt");
 
     #[test]
+    #[ignore = "fill in example.txt and the expected answers first"]
     fn test_examples() {
-      // assert_eq!(solve_part_one(EXAMPLE), 0);
-      // assert_eq!(solve_part_two(EXAMPLE), 0);
+      assert_eq!(Day::solve_part_one(EXAMPLE), Ok(0));
+      assert_eq!(Day::solve_part_two(EXAMPLE), Ok(0));
     }
 
     #[test]
+    #[ignore = "record the answers with `cargo xtask answer` first"]
     fn test_input() {
-      // assert_eq!(solve_part_one(INPUT), 0);
-      // assert_eq!(solve_part_two(INPUT), 0);
+      aoc::answers::assert::<Day>(ANSWERS, {day}, INPUT);
     }
   }
 "#};
@@ -57,6 +72,7 @@ struct Paths {
   module: PathBuf,
   example: PathBuf,
   input: PathBuf,
+  answers: PathBuf,
 }
 
 #[derive(Debug)]
@@ -77,12 +93,14 @@ impl Day {
     let module = format!("{root}/mod.rs");
     let example = format!("{root}/example.txt");
     let input = format!("{root}/input.txt");
+    let answers = format!("aoc_{year}/answers.toml", year = self.year);
 
     Paths {
       root: PathBuf::from(root),
       module: PathBuf::from(module),
       example: PathBuf::from(example),
       input: PathBuf::from(input),
+      answers: PathBuf::from(answers),
     }
   }
 
@@ -103,6 +121,11 @@ impl Day {
     fs::write(&paths.example, "")?;
     fs::write(&paths.input, "")?;
 
+    // The tests of the new day include the year's answers, so they must exist to compile.
+    if !paths.answers.exists() {
+      fs::write(&paths.answers, ANSWERS_TEMPLATE)?;
+    }
+
     Ok(())
   }
 }
@@ -1,3 +1,4 @@
+mod answer;
 mod day;
 
 use clap::{Parser, Subcommand};
@@ -11,7 +12,10 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Task {
+  /// Scaffolds a new day.
   Day,
+  /// Records an answer into the answers manifest of a year.
+  Answer,
 }
 
 fn main() {
@@ -20,6 +24,7 @@ fn main() {
   if let Some(task) = &cli.task {
     match task {
       | Task::Day => day::run(),
+      | Task::Answer => answer::run(),
     }
   } else {
     println!("No task specified.");